@@ -0,0 +1,33 @@
+//! Compares the field helpers with per-call constants against the `*_with` variants that reuse
+//! one precomputed `FieldConsts`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use kimchi::mina_curves::pasta::Fp;
+use sha256_kimchi::sha_helpers::*;
+
+fn word(x: u32) -> [Fp; 32] {
+    bits_to_field(&to_bits_be::<_, 32>(x))
+}
+
+fn field_consts_bench(c: &mut Criterion) {
+    let (a, b) = (word(0xdead_beef), word(0x0123_4567));
+    let consts = FieldConsts::<Fp>::new();
+
+    let mut group = c.benchmark_group("field_consts");
+    group.bench_function("xor", |bench| {
+        bench.iter(|| xor(black_box(a), black_box(b)))
+    });
+    group.bench_function("xor_with", |bench| {
+        bench.iter(|| xor_with(&consts, black_box(a), black_box(b)))
+    });
+    group.bench_function("wrapping_add", |bench| {
+        bench.iter(|| wrapping_add(black_box(a), black_box(b)))
+    });
+    group.bench_function("wrapping_add_with", |bench| {
+        bench.iter(|| wrapping_add_with(&consts, black_box(a), black_box(b)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, field_consts_bench);
+criterion_main!(benches);
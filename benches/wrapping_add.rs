@@ -0,0 +1,26 @@
+//! Compares the ripple-carry `wrapping_add` against the native-integer `wrapping_add_fast`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use kimchi::mina_curves::pasta::Fp;
+use sha256_kimchi::sha_helpers::*;
+
+fn word(x: u32) -> [Fp; 32] {
+    bits_to_field(&to_bits_be::<_, 32>(x))
+}
+
+fn wrapping_add_bench(c: &mut Criterion) {
+    // The all-ones operand makes the carry ripple through every bit.
+    let (a, b) = (word(u32::MAX), word(1));
+
+    let mut group = c.benchmark_group("wrapping_add");
+    group.bench_function("ripple", |bench| {
+        bench.iter(|| wrapping_add(black_box(a), black_box(b)))
+    });
+    group.bench_function("fast", |bench| {
+        bench.iter(|| wrapping_add_fast(black_box(a), black_box(b)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, wrapping_add_bench);
+criterion_main!(benches);
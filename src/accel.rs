@@ -0,0 +1,48 @@
+//! Fast byte-level SHA256 oracle, behind the `hw-accel` feature.
+//!
+//! Delegates to the `sha2` crate, which detects SHA-NI (x86) and the ARMv8 SHA extensions at
+//! runtime and falls back to portable code elsewhere. This path is only a fast reference: the
+//! field engines remain the canonical, circuit-faithful implementation, and [`selftest`] checks
+//! that the two agree.
+
+use ark_ff::PrimeField;
+use sha2::{Digest, Sha256};
+
+use crate::{errors::Sha256Error, native_sha256::NativeSha256, sha_helpers::digest_to_bytes};
+
+/// Fixed vectors for [`selftest`]: empty, one block and a two-block padding boundary.
+const SELFTEST_VECTORS: [&[u8]; 3] = [
+    b"",
+    b"abc",
+    b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+];
+
+/// Hashes `data` with the accelerated byte-level implementation.
+#[must_use]
+pub fn hash_bytes(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// Hashes fixed vectors with both the accelerated path and the field engine and checks that
+/// the digests agree, so the oracle cannot silently drift from the field logic.
+pub fn selftest<F: PrimeField>() -> Result<(), Sha256Error> {
+    for (vector, data) in SELFTEST_VECTORS.iter().enumerate() {
+        if hash_bytes(data) != digest_to_bytes(NativeSha256::<F>::hash_bytes(data)) {
+            return Err(Sha256Error::SelftestMismatch { vector });
+        }
+    }
+    Ok(())
+}
+
+/// Tests that the oracle matches a known digest and the field engine.
+#[test]
+fn selftest_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    assert_eq!(
+        hex::encode(hash_bytes(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        "Mismatch on the accelerated digest."
+    );
+    assert_eq!(selftest::<Fp>(), Ok(()), "Selftest failed.");
+}
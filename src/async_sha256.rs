@@ -0,0 +1,118 @@
+use std::{
+    future::poll_fn,
+    io,
+    pin::{pin, Pin},
+    task::{Context, Poll},
+};
+
+use ark_ff::PrimeField;
+use futures_core::Stream;
+use futures_io::AsyncWrite;
+
+use crate::{digest::Digest, native_sha256::StreamingSha256};
+
+/// Async adapter over [`StreamingSha256`]: an [`AsyncWrite`] sink that absorbs
+/// everything written to it and yields the digest on [`AsyncSha256::finalize`].
+///
+/// Hashing is CPU-bound and never waits, so every write completes immediately, compressing the
+/// blocks it fills inline. The digest equals that of the synchronous streaming hasher fed the
+/// same bytes.
+#[derive(Clone, Default)]
+pub struct AsyncSha256<F: PrimeField> {
+    hasher: StreamingSha256<F>,
+}
+
+impl<F: PrimeField> AsyncSha256<F> {
+    /// Creates an adapter over a fresh streaming hasher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the digest of everything written so far.
+    #[must_use]
+    pub fn finalize(self) -> Digest<F> {
+        self.hasher.finalize()
+    }
+}
+
+// The adapter never hands out pinned references to its fields, so it can be moved freely
+// whatever the field type.
+impl<F: PrimeField> Unpin for AsyncSha256<F> {}
+
+impl<F: PrimeField> AsyncWrite for AsyncSha256<F> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().hasher.update(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Drives a stream of byte chunks (e.g. `Bytes` frames off a socket) through the streaming
+/// hasher and resolves to the digest of their concatenation once the stream ends.
+pub async fn hash_stream<F, S>(stream: S) -> Digest<F>
+where
+    F: PrimeField,
+    S: Stream,
+    S::Item: AsRef<[u8]>,
+{
+    let mut stream = pin!(stream);
+    let mut hasher = StreamingSha256::<F>::new();
+    while let Some(chunk) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        hasher.update(chunk.as_ref());
+    }
+    hasher.finalize()
+}
+
+/// Tests that the async adapters match the synchronous streaming hash.
+#[test]
+fn async_sha256_test() {
+    use crate::native_sha256::NativeSha256;
+    use futures::{executor::block_on, io::AsyncWriteExt, stream};
+    use kimchi::mina_curves::pasta::Fp;
+
+    let data: Vec<u8> = (0..500u32).map(|i| (i % 251) as u8).collect();
+    let mut sync = StreamingSha256::<Fp>::new();
+    sync.update(&data);
+    let expected = sync.finalize();
+
+    // Chunk sizes that split blocks mid-way and align with them.
+    for chunk_size in [1, 63, 64, 100, 500] {
+        let written = block_on(async {
+            let mut writer = AsyncSha256::<Fp>::new();
+            for chunk in data.chunks(chunk_size) {
+                writer.write_all(chunk).await.unwrap();
+            }
+            writer.close().await.unwrap();
+            writer.finalize()
+        });
+        assert_eq!(
+            written, expected,
+            "AsyncWrite mismatch with {chunk_size}-byte chunks."
+        );
+
+        let streamed = block_on(hash_stream::<Fp, _>(stream::iter(
+            data.chunks(chunk_size).map(<[u8]>::to_vec),
+        )));
+        assert_eq!(
+            streamed, expected,
+            "Stream mismatch with {chunk_size}-byte chunks."
+        );
+    }
+
+    assert_eq!(
+        block_on(hash_stream::<Fp, _>(stream::empty::<Vec<u8>>())),
+        NativeSha256::<Fp>::hash_bytes(&[]),
+        "Mismatch on an empty stream."
+    );
+}
@@ -0,0 +1,62 @@
+//! Hashes stdin and prints the hex digest in the `sha256sum` format.
+//!
+//! Usage: `sha256 [--check EXPECTED]`. With `--check`, exits with status 1 if the digest does
+//! not match `EXPECTED` (case-insensitive hex).
+
+use std::{
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use kimchi::mina_curves::pasta::Fp;
+use sha256_kimchi::{native_sha256::StreamingSha256, sha_helpers::digest_to_hex};
+
+const USAGE: &str = "Usage: sha256 [--check EXPECTED] < FILE";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let expected = match (args.next().as_deref(), args.next(), args.next()) {
+        (None, _, _) => None,
+        (Some("--check"), Some(expected), None) => Some(expected.to_lowercase()),
+        (Some("-h" | "--help"), None, _) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut hasher = StreamingSha256::<Fp>::new();
+    let mut stdin = io::stdin().lock();
+    let mut chunk = [0u8; 8192];
+    loop {
+        match stdin.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => hasher.update(&chunk[..n]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                eprintln!("sha256: failed to read stdin: {err}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+    let digest = digest_to_hex(hasher.finalize());
+
+    match expected {
+        None => {
+            println!("{digest}  -");
+            ExitCode::SUCCESS
+        }
+        Some(expected) if expected == digest => {
+            println!("-: OK");
+            ExitCode::SUCCESS
+        }
+        Some(_) => {
+            println!("-: FAILED");
+            eprintln!("sha256: computed digest {digest} does not match");
+            ExitCode::from(1)
+        }
+    }
+}
@@ -0,0 +1,178 @@
+#![allow(non_snake_case)]
+
+use ark_ff::PrimeField;
+
+use crate::{
+    constants::{initial_state_u32, round_constants_u32},
+    digest::Digest,
+    sha_helpers::{bytes_to_bits, min_padded_bits, sha256_pad},
+};
+
+/// A 32-bit word stored as one `bool` per bit, most significant bit first.
+pub type BoolWord = [bool; 32];
+
+/// A SHA256 digest or chaining state in `bool` form, the layout of [`Digest`] without fields.
+pub type BoolDigest = [BoolWord; 8];
+
+/// Bitwise AND.
+pub fn and(a: BoolWord, b: BoolWord) -> BoolWord {
+    std::array::from_fn(|i| a[i] & b[i])
+}
+
+/// Bitwise XOR.
+pub fn xor(a: BoolWord, b: BoolWord) -> BoolWord {
+    std::array::from_fn(|i| a[i] ^ b[i])
+}
+
+/// Bitwise NOT.
+pub fn not(a: BoolWord) -> BoolWord {
+    a.map(|bit| !bit)
+}
+
+/// Rotates the word right by `rot` bits.
+pub fn rotate_right(rot: usize, word: BoolWord) -> BoolWord {
+    std::array::from_fn(|i| word[(i + 32 - rot % 32) % 32])
+}
+
+/// Shifts the word right by `shift` bits, filling with zeros.
+pub fn right_shift(shift: usize, word: BoolWord) -> BoolWord {
+    std::array::from_fn(|i| i >= shift && word[i - shift])
+}
+
+/// Modular addition (mod 2^32) with the same ripple carry as the field version.
+pub fn wrapping_add(a: BoolWord, b: BoolWord) -> BoolWord {
+    let mut result = [false; 32];
+    let mut carry = false;
+    for i in (0..32).rev() {
+        result[i] = a[i] ^ b[i] ^ carry;
+        carry = (a[i] & b[i]) | (carry & (a[i] ^ b[i]));
+    }
+    result
+}
+
+/// Expands a `u32` into a word.
+fn word(x: u32) -> BoolWord {
+    std::array::from_fn(|i| (x >> (31 - i)) & 1 == 1)
+}
+
+/// Applies the SHA256 compression function to a 512-bit block.
+pub fn compress_block(state: BoolDigest, block: &[bool]) -> BoolDigest {
+    assert_eq!(block.len(), 512, "Chunk must be 512 bits");
+
+    let mut W = [[false; 32]; 64];
+    for (i, chunk) in block.chunks_exact(32).enumerate() {
+        W[i].copy_from_slice(chunk);
+    }
+    for i in 16..64 {
+        let s0 = xor(
+            xor(rotate_right(7, W[i - 15]), rotate_right(18, W[i - 15])),
+            right_shift(3, W[i - 15]),
+        );
+        let s1 = xor(
+            xor(rotate_right(17, W[i - 2]), rotate_right(19, W[i - 2])),
+            right_shift(10, W[i - 2]),
+        );
+        W[i] = wrapping_add(wrapping_add(s1, W[i - 7]), wrapping_add(s0, W[i - 16]));
+    }
+
+    let K = round_constants_u32();
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+    for i in 0..64 {
+        let S1 = xor(
+            xor(rotate_right(6, e), rotate_right(11, e)),
+            rotate_right(25, e),
+        );
+        let Ch = xor(and(e, f), and(not(e), g));
+        let T1 = wrapping_add(
+            wrapping_add(wrapping_add(wrapping_add(h, S1), Ch), word(K[i])),
+            W[i],
+        );
+        let S0 = xor(
+            xor(rotate_right(2, a), rotate_right(13, a)),
+            rotate_right(22, a),
+        );
+        let Maj = xor(xor(and(a, b), and(a, c)), and(b, c));
+        let T2 = wrapping_add(S0, Maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = wrapping_add(d, T1);
+        d = c;
+        c = b;
+        b = a;
+        a = wrapping_add(T1, T2);
+    }
+
+    let raw = [a, b, c, d, e, f, g, h];
+    std::array::from_fn(|i| wrapping_add(raw[i], state[i]))
+}
+
+/// Hashes a byte message with the `bool` backend, padding it according to its true length.
+#[must_use]
+pub fn hash_bytes(data: &[u8]) -> BoolDigest {
+    let bits = bytes_to_bits(data);
+    let max_bits = min_padded_bits(bits.len());
+    let (padded, _) = sha256_pad(bits, max_bits).expect("Minimal padding always fits.");
+
+    let padded: Vec<bool> = padded.into_iter().map(|bit| bit == 1).collect();
+    padded
+        .chunks(512)
+        .fold(initial_state_u32().map(word), |state, block| {
+            compress_block(state, block)
+        })
+}
+
+/// Converts a `bool` digest into its field form.
+pub fn to_field<F: PrimeField>(digest: BoolDigest) -> Digest<F> {
+    digest.map(|word| word.map(|bit| if bit { F::one() } else { F::zero() }))
+}
+
+/// Converts a field digest into its `bool` form; any nonzero element reads as a set bit.
+pub fn from_field<F: PrimeField>(digest: Digest<F>) -> BoolDigest {
+    digest.map(|word| word.map(|bit| !bit.is_zero()))
+}
+
+/// Converts a `bool` digest into its 32 big-endian bytes.
+pub fn to_bytes(digest: BoolDigest) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (byte, bits) in bytes.iter_mut().zip(digest.as_flattened().chunks_exact(8)) {
+        *byte = bits.iter().fold(0, |acc, &bit| (acc << 1) | bit as u8);
+    }
+    bytes
+}
+
+/// Tests that the `bool` and field backends agree.
+#[test]
+fn bool_backend_test() {
+    use crate::{native_sha256::NativeSha256, sha_helpers::digest_to_bytes};
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    for len in [0usize, 3, 55, 56, 64, 100] {
+        let data: Vec<u8> = (0..len as u8).collect();
+        let bool_digest = hash_bytes(&data);
+        let field_digest = NativeSha256::<Fp>::hash_bytes(&data);
+
+        assert_eq!(
+            to_bytes(bool_digest),
+            digest_to_bytes(field_digest),
+            "Backends diverge on {len} bytes."
+        );
+        assert_eq!(
+            to_bytes(bool_digest),
+            <[u8; 32]>::from(Sha256::digest(&data)),
+            "Mismatch with standard SHA256 on {len} bytes."
+        );
+        assert_eq!(
+            to_field::<Fp>(bool_digest),
+            field_digest,
+            "Mismatch on conversion to fields."
+        );
+        assert_eq!(
+            from_field(field_digest),
+            bool_digest,
+            "Mismatch on conversion from fields."
+        );
+    }
+}
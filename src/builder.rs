@@ -0,0 +1,295 @@
+#![allow(non_snake_case)]
+
+use ark_ff::PrimeField;
+
+use crate::{
+    compression::compress_block_rounds, constants::*, digest::Digest, errors::Sha256Error,
+    sha_helpers::*,
+};
+
+/// Digest variant: selects the default initial state and the output length.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    /// SHA256, with a 32-byte output.
+    #[default]
+    Sha256,
+    /// SHA224: the SHA256 compression with its own initial state, truncated to 28 bytes.
+    Sha224,
+}
+
+impl Variant {
+    /// Number of output bytes of the variant.
+    pub fn output_len(self) -> usize {
+        match self {
+            Variant::Sha256 => 32,
+            Variant::Sha224 => 28,
+        }
+    }
+}
+
+/// Byte order used when serializing each 32-bit output word.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endian {
+    /// Standard SHA256 output: every word big-endian.
+    #[default]
+    Big,
+    /// Every word little-endian, as expected by some circuit and VM interfaces.
+    Little,
+}
+
+/// Builder for nonstandard hasher configurations.
+/// The defaults reproduce standard SHA256.
+#[derive(Clone, Debug)]
+pub struct Sha256Builder<F: PrimeField> {
+    variant: Variant,
+    init_state: Option<[[F; 32]; 8]>,
+    round_constants: Option<Box<[[F; 32]; 64]>>,
+    rounds: usize,
+    output_endian: Endian,
+}
+
+impl<F: PrimeField> Default for Sha256Builder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> Sha256Builder<F> {
+    /// Creates a builder for standard SHA256.
+    pub fn new() -> Self {
+        Self {
+            variant: Variant::default(),
+            init_state: None,
+            round_constants: None,
+            rounds: 64,
+            output_endian: Endian::default(),
+        }
+    }
+
+    /// Sets the digest variant.
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Overrides the initial state of the variant.
+    pub fn init_state(mut self, state: [[F; 32]; 8]) -> Self {
+        self.init_state = Some(state);
+        self
+    }
+
+    /// Overrides the round constants `K`, e.g. to inject the constants of an intermediate test
+    /// vector when testing the compression core in isolation.
+    pub fn round_constants(mut self, K: [[F; 32]; 64]) -> Self {
+        self.round_constants = Some(Box::new(K));
+        self
+    }
+
+    /// Sets the number of compression rounds per block, between 1 and 64.
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Sets the byte order of the output words.
+    pub fn output_endian(mut self, endian: Endian) -> Self {
+        self.output_endian = endian;
+        self
+    }
+
+    /// Validates the configuration and returns the configured hasher.
+    pub fn build(self) -> Result<ConfiguredSha256<F>, Sha256Error> {
+        if !(1..=64).contains(&self.rounds) {
+            return Err(Sha256Error::InvalidRounds {
+                rounds: self.rounds,
+            });
+        }
+
+        let state = match self.init_state {
+            Some(state) => {
+                validate_state(&state)?;
+                state
+            }
+            None => match self.variant {
+                Variant::Sha256 => initial_state(),
+                Variant::Sha224 => initial_state_224(),
+            },
+        };
+
+        let K = match self.round_constants {
+            Some(K) => {
+                validate_state(&K)?;
+                K
+            }
+            None => Box::new(round_constants()),
+        };
+
+        Ok(ConfiguredSha256 {
+            variant: self.variant,
+            state,
+            K,
+            rounds: self.rounds,
+            output_endian: self.output_endian,
+        })
+    }
+}
+
+/// Hasher assembled by [`Sha256Builder`].
+#[derive(Clone, Debug)]
+pub struct ConfiguredSha256<F: PrimeField> {
+    variant: Variant,
+    state: [[F; 32]; 8],
+    K: Box<[[F; 32]; 64]>,
+    rounds: usize,
+    output_endian: Endian,
+}
+
+impl<F: PrimeField> ConfiguredSha256<F> {
+    /// Computes the final chaining state over an already padded bitstream, before any
+    /// truncation or output reordering.
+    #[must_use]
+    pub fn hash(&self, padded_preimage: &[u8]) -> Digest<F> {
+        assert!(
+            padded_preimage.len().is_multiple_of(512),
+            "Input must be padded to 512-bit blocks."
+        );
+
+        padded_preimage
+            .chunks(512)
+            .fold(self.state, |state, chunk| {
+                compress_block_rounds(state, chunk, *self.K, self.rounds)
+            })
+    }
+
+    /// Pads and hashes `data`, returning the digest bytes of the configured variant with each
+    /// word in the configured byte order.
+    #[must_use]
+    pub fn hash_bytes(&self, data: &[u8]) -> Vec<u8> {
+        let bits = bytes_to_bits(data);
+        let max_bits = min_padded_bits(bits.len());
+        let (padded, _) = sha256_pad(bits, max_bits).expect("max_bits fits the input");
+
+        let mut bytes = digest_to_bytes(self.hash(&padded));
+        if self.output_endian == Endian::Little {
+            bytes.chunks_exact_mut(4).for_each(|word| word.reverse());
+        }
+        bytes[..self.variant.output_len()].to_vec()
+    }
+}
+
+/// Tests builder combinations against reference digests.
+#[test]
+fn builder_test() {
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha224, Sha256};
+
+    let msg = b"builder";
+
+    // Defaults reproduce SHA256.
+    let standard = Sha256Builder::<Fp>::new().build().unwrap();
+    assert_eq!(
+        standard.hash_bytes(msg),
+        Sha256::digest(msg).to_vec(),
+        "Mismatch on default configuration."
+    );
+
+    // SHA224 variant.
+    let sha224 = Sha256Builder::<Fp>::new()
+        .variant(Variant::Sha224)
+        .build()
+        .unwrap();
+    assert_eq!(
+        sha224.hash_bytes(msg),
+        Sha224::digest(msg).to_vec(),
+        "Mismatch on SHA224 variant."
+    );
+
+    // Little-endian words.
+    let little = Sha256Builder::<Fp>::new()
+        .output_endian(Endian::Little)
+        .build()
+        .unwrap();
+    let expected: Vec<u8> = Sha256::digest(msg)
+        .chunks(4)
+        .flat_map(|word| word.iter().rev().copied())
+        .collect();
+    assert_eq!(
+        little.hash_bytes(msg),
+        expected,
+        "Mismatch on little-endian output."
+    );
+
+    // Explicit standard IV and full rounds change nothing; fewer rounds do.
+    let explicit = Sha256Builder::<Fp>::new()
+        .init_state(initial_state())
+        .rounds(64)
+        .build()
+        .unwrap();
+    assert_eq!(
+        explicit.hash_bytes(msg),
+        standard.hash_bytes(msg),
+        "Explicit defaults must reproduce SHA256."
+    );
+    let reduced = Sha256Builder::<Fp>::new().rounds(16).build().unwrap();
+    assert_ne!(
+        reduced.hash_bytes(msg),
+        standard.hash_bytes(msg),
+        "Reduced rounds must change the digest."
+    );
+
+    // Invalid configurations.
+    assert_eq!(
+        Sha256Builder::<Fp>::new().rounds(0).build().err(),
+        Some(Sha256Error::InvalidRounds { rounds: 0 }),
+        "Zero rounds accepted."
+    );
+    assert_eq!(
+        Sha256Builder::<Fp>::new().rounds(65).build().err(),
+        Some(Sha256Error::InvalidRounds { rounds: 65 }),
+        "More than 64 rounds accepted."
+    );
+    let mut state = initial_state::<Fp>();
+    state[0][0] = Fp::from(3u8);
+    assert_eq!(
+        Sha256Builder::new().init_state(state).build().err(),
+        Some(Sha256Error::NonBooleanState { word: 0, bit: 0 }),
+        "Non-boolean initial state accepted."
+    );
+}
+
+/// Tests injecting custom round constants.
+#[test]
+fn round_constants_test() {
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    let msg = b"round constants";
+    let standard = Sha256Builder::<Fp>::new()
+        .round_constants(round_constants())
+        .build()
+        .unwrap();
+    assert_eq!(
+        standard.hash_bytes(msg),
+        Sha256::digest(msg).to_vec(),
+        "Standard constants must reproduce SHA256."
+    );
+
+    let mut K = round_constants::<Fp>();
+    K[63][31] = Fp::from(1u8) - K[63][31];
+    let altered = Sha256Builder::<Fp>::new()
+        .round_constants(K)
+        .build()
+        .unwrap();
+    assert_ne!(
+        altered.hash_bytes(msg),
+        standard.hash_bytes(msg),
+        "Altering one constant bit must change the digest."
+    );
+
+    K[5][0] = Fp::from(2u8);
+    assert_eq!(
+        Sha256Builder::new().round_constants(K).build().err(),
+        Some(Sha256Error::NonBooleanState { word: 5, bit: 0 }),
+        "Expected a non-boolean constant error."
+    );
+}
@@ -0,0 +1,90 @@
+use ark_ff::PrimeField;
+#[cfg(feature = "commitment")]
+use rand::Rng;
+
+use crate::{digest::Digest, native_sha256::StreamingSha256, sha_helpers::*};
+
+/// Computes a hiding commitment `SHA256(salt || message)` with a fresh 32-byte random salt.
+/// Returns the salt, which must be kept to open the commitment, together with the digest.
+#[cfg(feature = "commitment")]
+#[must_use]
+pub fn commit<F: PrimeField>(message: &[u8], rng: &mut impl Rng) -> ([u8; 32], Digest<F>) {
+    let salt: [u8; 32] = rng.gen();
+    (salt, commit_with_salt(message, &salt))
+}
+
+/// Computes the commitment `SHA256(salt || message)` under a caller-chosen salt, which must be
+/// fresh and uniformly random for the commitment to hide `message`.
+#[must_use]
+pub fn commit_with_salt<F: PrimeField>(message: &[u8], salt: &[u8; 32]) -> Digest<F> {
+    let mut hasher = StreamingSha256::new();
+    hasher.update(salt);
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Checks that `digest` opens to `message` under `salt`, comparing in constant time.
+#[must_use]
+pub fn verify_commitment<F: PrimeField>(
+    message: &[u8],
+    salt: &[u8; 32],
+    digest: Digest<F>,
+) -> bool {
+    constant_time_eq(
+        &digest_to_bytes(commit_with_salt::<F>(message, salt)),
+        &digest_to_bytes(digest),
+    )
+}
+
+/// Tests that commitments round-trip and reject a wrong salt.
+#[test]
+fn commitment_test() {
+    use kimchi::{mina_curves::pasta::Fp, o1_utils::tests};
+    use rand::Rng;
+    use sha2::{Digest, Sha256};
+
+    let mut rng = tests::make_test_rng(None);
+    let message = b"commit to me";
+    let salt: [u8; 32] = rng.gen();
+    let digest = commit_with_salt::<Fp>(message, &salt);
+
+    assert_eq!(
+        digest_to_hex(digest),
+        hex::encode(Sha256::digest([&salt[..], message].concat())),
+        "Mismatch between commitment and standard SHA256."
+    );
+    assert!(
+        verify_commitment(message, &salt, digest),
+        "Valid opening rejected."
+    );
+
+    let mut wrong_salt = salt;
+    wrong_salt[0] ^= 1;
+    assert!(
+        !verify_commitment(message, &wrong_salt, digest),
+        "Wrong salt accepted."
+    );
+    assert!(
+        !verify_commitment(b"commit to m3", &salt, digest),
+        "Wrong message accepted."
+    );
+}
+
+/// Tests that `commit` returns a salt that opens its digest.
+#[cfg(feature = "commitment")]
+#[test]
+fn commit_test() {
+    use kimchi::{mina_curves::pasta::Fp, o1_utils::tests};
+
+    let mut rng = tests::make_test_rng(None);
+    let (salt, digest) = commit::<Fp>(b"commit to me", &mut rng);
+    assert_eq!(
+        digest,
+        commit_with_salt(b"commit to me", &salt),
+        "Mismatch between commit and its salt."
+    );
+    assert!(
+        verify_commitment(b"commit to me", &salt, digest),
+        "Valid opening rejected."
+    );
+}
@@ -0,0 +1,437 @@
+#![allow(non_snake_case)]
+
+use ark_ff::PrimeField;
+
+use crate::{
+    params::{self, Sha256Params},
+    sha_helpers::*,
+};
+
+/// Word-level operation counts of a SHA256 computation, for budgeting circuit size.
+/// Each operation acts on a 32-bit word. `and` includes the one product inside every `xor`
+/// (computed as `a + b - 2ab`), so it counts every bitwise multiplication.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConstraintEstimate {
+    pub blocks: usize,
+    pub xor: usize,
+    pub and: usize,
+    pub not: usize,
+    pub add: usize,
+}
+
+impl ConstraintEstimate {
+    /// Total number of word-level operations.
+    pub fn total(&self) -> usize {
+        self.xor + self.and + self.not + self.add
+    }
+}
+
+/// Estimates the operation counts of hashing a message of `input_bits_len` bits with minimal
+/// padding, from the fixed structure of the compression function alone.
+///
+/// Per block, the 48 expanded schedule words cost 4 XORs and 3 additions each; each of the 64
+/// rounds costs 7 XORs (`Σ1`, `Ch`, `Σ0`, `Maj`), 5 ANDs, 1 NOT and 7 additions (`T1`, `T2`, `e`,
+/// `a`); the feed-forward adds 8 more additions.
+#[must_use]
+pub fn estimate_constraints(input_bits_len: usize) -> ConstraintEstimate {
+    const SCHEDULE_WORDS: usize = 48;
+    const ROUNDS: usize = 64;
+
+    let blocks = min_padded_bits(input_bits_len) / 512;
+    let xor = SCHEDULE_WORDS * 4 + ROUNDS * 7;
+
+    ConstraintEstimate {
+        blocks,
+        xor: blocks * xor,
+        and: blocks * (ROUNDS * 5 + xor),
+        not: blocks * ROUNDS,
+        add: blocks * (SCHEDULE_WORDS * 3 + ROUNDS * 7 + 8),
+    }
+}
+
+/// Expands a 512-bit block into the 64-word SHA256 message schedule `W`.
+#[must_use]
+pub fn message_schedule<F: PrimeField>(bits: &[u8]) -> [[F; 32]; 64] {
+    assert_eq!(bits.len(), 512, "Chunk must be 512 bits");
+
+    let mut W = [[F::zero(); 32]; 64];
+    for (word, chunk) in W.iter_mut().zip(bits.chunks_exact(32)) {
+        *word = bits_to_field(chunk);
+    }
+    params::expand_schedule::<F, 32, Sha256Params>(&mut W);
+    W
+}
+
+/// Expands a 512-bit block like [`message_schedule`] and decodes each word `W[i]` to a `u32`,
+/// for comparing the schedule against values computed outside the field.
+#[must_use]
+pub fn message_schedule_words<F: PrimeField>(block: &[u8; 512]) -> [u32; 64] {
+    message_schedule::<F>(block).map(bits_to_u32)
+}
+
+/// Runs the 64 SHA256 rounds over a 512-bit block and returns the working variables `a..h`
+/// *before* the feed-forward addition, i.e. the bare output of the block cipher `E(block, state)`
+/// in SHA256's Davies–Meyer construction.
+#[must_use]
+pub fn compress_block_raw<F: PrimeField>(
+    state: [[F; 32]; 8],
+    bits: &[u8],
+    K: [[F; 32]; 64],
+) -> [[F; 32]; 8] {
+    let W = message_schedule::<F>(bits);
+    run_rounds(state, &W, K, 64, |_| {})
+}
+
+/// Applies [`compress_block`] to a block whose schedule was already expanded with
+/// [`message_schedule`], so several states can share one expansion of the same block.
+#[must_use]
+pub fn compress_with_schedule<F: PrimeField>(
+    state: [[F; 32]; 8],
+    W: &[[F; 32]; 64],
+    K: [[F; 32]; 64],
+) -> [[F; 32]; 8] {
+    let raw = run_rounds(state, W, K, 64, |_| {});
+    std::array::from_fn(|i| wrapping_add(raw[i], state[i]))
+}
+
+/// Applies the SHA256 compression function with only the first `rounds` rounds, including the
+/// feed-forward addition. With `rounds = 64` this is [`compress_block`]; fewer rounds give the
+/// reduced-round variants used in cryptanalysis and circuit experiments.
+#[must_use]
+pub fn compress_block_rounds<F: PrimeField>(
+    state: [[F; 32]; 8],
+    bits: &[u8],
+    K: [[F; 32]; 64],
+    rounds: usize,
+) -> [[F; 32]; 8] {
+    assert!(rounds <= 64, "SHA256 has at most 64 rounds!");
+
+    let W = message_schedule::<F>(bits);
+    let raw = run_rounds(state, &W, K, rounds, |_| {});
+    std::array::from_fn(|i| wrapping_add(raw[i], state[i]))
+}
+
+/// Intermediate values of one round over `W`-bit words, 32-bit for SHA256.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundTrace<F: PrimeField, const W: usize = 32> {
+    /// Working variables `a..h` at the start of the round.
+    pub working: [[F; W]; 8],
+    /// `T1 = h + Σ1(e) + Ch(e, f, g) + K_i + W_i`.
+    pub T1: [F; W],
+    /// `T2 = Σ0(a) + Maj(a, b, c)`.
+    pub T2: [F; W],
+    /// Message schedule word `W_i`.
+    pub W: [F; W],
+}
+
+impl<F: PrimeField, const W: usize> RoundTrace<F, W> {
+    /// Flattens the round into one witness row with columns `[a, b, c, d, e, f, g, h, T1, T2, W_i]`,
+    /// each a `W`-bit big-endian word, for `11 * W` field elements in total (352 for SHA256).
+    pub fn to_row(&self) -> Vec<F> {
+        self.working
+            .iter()
+            .chain([&self.T1, &self.T2, &self.W])
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+/// Intermediate values of one block compression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockTrace<F: PrimeField> {
+    /// The 64-word message schedule of the block.
+    pub schedule: [[F; 32]; 64],
+    /// The 64 rounds, in order.
+    pub rounds: Vec<RoundTrace<F>>,
+    /// The chaining state after the feed-forward addition.
+    pub output: [[F; 32]; 8],
+}
+
+/// Traces of selected blocks, each paired with its block index.
+pub type BlockTraces<F> = Vec<(usize, BlockTrace<F>)>;
+
+/// Applies [`compress_block`] while recording every round's intermediate values.
+#[must_use]
+pub fn compress_block_traced<F: PrimeField>(
+    state: [[F; 32]; 8],
+    bits: &[u8],
+    K: [[F; 32]; 64],
+) -> BlockTrace<F> {
+    trace_rounds(state, bits, K, 64)
+}
+
+/// Applies [`compress_block_rounds`] while recording every round's intermediate values.
+fn trace_rounds<F: PrimeField>(
+    state: [[F; 32]; 8],
+    bits: &[u8],
+    K: [[F; 32]; 64],
+    rounds: usize,
+) -> BlockTrace<F> {
+    let schedule = message_schedule::<F>(bits);
+    let mut trace = Vec::with_capacity(rounds);
+    let raw = run_rounds(state, &schedule, K, rounds, |round| trace.push(round));
+
+    BlockTrace {
+        schedule,
+        rounds: trace,
+        output: std::array::from_fn(|i| wrapping_add(raw[i], state[i])),
+    }
+}
+
+/// Runs the first `rounds` SHA256 rounds over an expanded schedule, reporting each round to
+/// `on_round`.
+fn run_rounds<F: PrimeField>(
+    state: [[F; 32]; 8],
+    W: &[[F; 32]; 64],
+    K: [[F; 32]; 64],
+    rounds: usize,
+    on_round: impl FnMut(RoundTrace<F>),
+) -> [[F; 32]; 8] {
+    params::run_rounds::<F, 32, Sha256Params>(state, W, &K, rounds, on_round)
+}
+
+/// Applies the full SHA256 compression function to a 512-bit block: the raw round output of
+/// [`compress_block_raw`] added word-wise (mod 2^32) to the input chaining state.
+#[must_use]
+pub fn compress_block<F: PrimeField>(
+    state: [[F; 32]; 8],
+    bits: &[u8],
+    K: [[F; 32]; 64],
+) -> [[F; 32]; 8] {
+    let raw = compress_block_raw(state, bits, K);
+    std::array::from_fn(|i| wrapping_add(raw[i], state[i]))
+}
+
+/// Role of a block within a padded message, for wiring the final block differently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockKind {
+    /// A block before the last one; its bits are not constrained.
+    Intermediate,
+    /// The last block of the minimal padding of a `message_bits`-bit message, ending in the
+    /// 64-bit length field.
+    Final { message_bits: usize },
+}
+
+/// Applies [`compress_block`] to a block of the given [`BlockKind`].
+///
+/// Panics if a final block is not laid out as [`sha256_pad`] would lay out the last block of a
+/// `message_bits`-bit message: the length field must hold `message_bits`, and the `1` marker and
+/// zero fill must follow the message, or fill the whole block up to the length field when the
+/// marker fell in the previous block.
+#[must_use]
+pub fn compress_block_typed<F: PrimeField>(
+    state: [[F; 32]; 8],
+    bits: &[u8],
+    K: [[F; 32]; 64],
+    kind: BlockKind,
+) -> [[F; 32]; 8] {
+    if let BlockKind::Final { message_bits } = kind {
+        assert_eq!(bits.len(), 512, "Chunk must be 512 bits");
+        let length_field: [u8; 64] = bits[448..].try_into().unwrap();
+        assert_eq!(
+            from_bits_be(&length_field),
+            message_bits as u64,
+            "Final block length field does not match the message length!"
+        );
+
+        let marker = message_bits % 512;
+        let fill = if marker < 448 {
+            assert_eq!(
+                bits[marker], 1,
+                "Final block lacks the 1 marker after the message!"
+            );
+            &bits[marker + 1..448]
+        } else {
+            &bits[..448]
+        };
+        assert!(
+            fill.iter().all(|bit| *bit == 0),
+            "Final block zero fill is not zero!"
+        );
+    }
+
+    compress_block(state, bits, K)
+}
+
+/// A block compression function the hashers can be parameterized with, e.g. to plug in a
+/// deliberately wrong implementation and check that a test suite catches it.
+/// The default methods are the standard [`compress_block_raw`] and [`compress_block_traced`];
+/// an implementation overriding one must override the other to match.
+pub trait Compressor<F: PrimeField> {
+    /// Runs the rounds over one 512-bit block and returns the working variables before the
+    /// feed-forward addition.
+    fn compress_raw(state: [[F; 32]; 8], bits: &[u8], K: [[F; 32]; 64]) -> [[F; 32]; 8] {
+        compress_block_raw(state, bits, K)
+    }
+
+    /// Compresses one 512-bit block into the chaining state.
+    fn compress(state: [[F; 32]; 8], bits: &[u8], K: [[F; 32]; 64]) -> [[F; 32]; 8] {
+        let raw = Self::compress_raw(state, bits, K);
+        std::array::from_fn(|i| wrapping_add(raw[i], state[i]))
+    }
+
+    /// Compresses one 512-bit block like [`Compressor::compress`], recording every round.
+    fn compress_traced(state: [[F; 32]; 8], bits: &[u8], K: [[F; 32]; 64]) -> BlockTrace<F> {
+        compress_block_traced(state, bits, K)
+    }
+}
+
+/// The standard SHA256 compression function, the default [`Compressor`] of the hashers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sha256Compressor;
+
+impl<F: PrimeField> Compressor<F> for Sha256Compressor {}
+
+/// A deliberately wrong [`Compressor`] that skips the last round, for negative tests: a
+/// differential test against `sha2` must fail for a hasher built on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BrokenCompressor;
+
+impl<F: PrimeField> Compressor<F> for BrokenCompressor {
+    fn compress_raw(state: [[F; 32]; 8], bits: &[u8], K: [[F; 32]; 64]) -> [[F; 32]; 8] {
+        run_rounds(state, &message_schedule(bits), K, 63, |_| {})
+    }
+
+    fn compress_traced(state: [[F; 32]; 8], bits: &[u8], K: [[F; 32]; 64]) -> BlockTrace<F> {
+        trace_rounds(state, bits, K, 63)
+    }
+}
+
+/// Tests that feeding the raw compression output forward reproduces the standard digest.
+#[test]
+fn compress_block_test() {
+    use crate::constants::*;
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    let (padded, _) = sha256_pad(bytes_to_bits(b"abc"), 512).unwrap();
+    let state = initial_state::<Fp>();
+    let K = round_constants();
+
+    let raw = compress_block_raw(state, &padded, K);
+    let fed_forward: [[Fp; 32]; 8] = std::array::from_fn(|i| wrapping_add(raw[i], state[i]));
+
+    assert_ne!(
+        raw, fed_forward,
+        "Raw output must differ from the fed-forward state."
+    );
+    assert_eq!(
+        fed_forward,
+        compress_block(state, &padded, K),
+        "Mismatch between raw + state and compress_block."
+    );
+    assert_eq!(
+        digest_to_hex(fed_forward),
+        hex::encode(Sha256::digest(b"abc")),
+        "Mismatch between compress_block and standard SHA256."
+    );
+}
+
+/// Tests the analytical operation counts against an instrumented compression.
+#[test]
+fn estimate_constraints_test() {
+    use crate::constants::*;
+    use kimchi::mina_curves::pasta::Fp;
+
+    let (padded, _) = sha256_pad(bytes_to_bits(b"abc"), 512).unwrap();
+    op_counts::take();
+    let _ = compress_block::<Fp>(initial_state(), &padded, round_constants());
+    let [xor, and, not, add] = op_counts::take();
+
+    let estimate = estimate_constraints(24);
+    assert_eq!(
+        estimate,
+        ConstraintEstimate {
+            blocks: 1,
+            xor,
+            and,
+            not,
+            add
+        },
+        "Mismatch between estimate and instrumented counts."
+    );
+    assert_eq!(
+        estimate_constraints(448).total(),
+        2 * estimate.total(),
+        "Two blocks must cost twice one block."
+    );
+}
+
+/// Tests the decoded schedule of the one-block "abc" message against FIPS 180-2, Appendix B.1.
+#[test]
+fn message_schedule_words_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let (padded, _) = sha256_pad(bytes_to_bits(b"abc"), 512).unwrap();
+    let expected: [u32; 64] = [
+        0x61626380, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000018, 0x61626380, 0x000f0000, 0x7da86405, 0x600003c6, 0x3e9d7b78,
+        0x0183fc00, 0x12dcbfdb, 0xe2e2c38e, 0xc8215c1a, 0xb73679a2, 0xe5bc3909, 0x32663c5b,
+        0x9d209d67, 0xec8726cb, 0x702138a4, 0xd3b7973b, 0x93f5997f, 0x3b68ba73, 0xaff4ffc1,
+        0xf10a5c62, 0x0a8b3996, 0x72af830a, 0x9409e33e, 0x24641522, 0x9f47bf94, 0xf0a64f5a,
+        0x3e246a79, 0x27333ba3, 0x0c4763f2, 0x840abf27, 0x7a290d5d, 0x065c43da, 0xfb3e89cb,
+        0xcc7617db, 0xb9e66c34, 0xa9993667, 0x84badedd, 0xc21462bc, 0x1487472c, 0xb20f7a99,
+        0xef57b9cd, 0xebe6b238, 0x9fe3095e, 0x78bc8d4b, 0xa43fcf15, 0x668b2ff8, 0xeeaba2cc,
+        0x12b1edeb,
+    ];
+
+    assert_eq!(
+        message_schedule_words::<Fp>(padded.as_slice().try_into().unwrap()),
+        expected,
+        "Mismatch on the expanded schedule."
+    );
+}
+
+/// Tests that well-formed final blocks pass the layout check and intermediate blocks skip it.
+#[test]
+fn compress_block_typed_test() {
+    use crate::constants::{initial_state, round_constants};
+    use kimchi::mina_curves::pasta::Fp;
+
+    let K = round_constants::<Fp>();
+    // The marker inside the final block, at its start, and in the previous block.
+    for len in [3, 64, 56] {
+        let message_bits = 8 * len;
+        let (padded, _) = sha256_pad(vec![1; message_bits], min_padded_bits(message_bits)).unwrap();
+        let blocks: Vec<&[u8]> = padded.chunks(512).collect();
+        let (last, intermediate) = blocks.split_last().unwrap();
+
+        let state = intermediate
+            .iter()
+            .fold(initial_state::<Fp>(), |state, block| {
+                compress_block_typed(state, block, K, BlockKind::Intermediate)
+            });
+        assert_eq!(
+            compress_block_typed(state, last, K, BlockKind::Final { message_bits }),
+            compress_block(state, last, K),
+            "Mismatch on the final block of {len} bytes."
+        );
+    }
+
+    // An intermediate block is not checked, even with a length field that means nothing.
+    let state = initial_state::<Fp>();
+    let noise = [1u8; 512];
+    assert_eq!(
+        compress_block_typed(state, &noise, K, BlockKind::Intermediate),
+        compress_block(state, &noise, K),
+        "Intermediate blocks must not be checked."
+    );
+}
+
+/// Tests that a final block whose length field disagrees with the message length is rejected.
+#[test]
+#[should_panic(expected = "length field does not match")]
+fn compress_block_typed_length_mismatch_test() {
+    use crate::constants::{initial_state, round_constants};
+    use kimchi::mina_curves::pasta::Fp;
+
+    let (padded, _) = sha256_pad(bytes_to_bits(b"abc"), 512).unwrap();
+    let _ = compress_block_typed::<Fp>(
+        initial_state(),
+        &padded,
+        round_constants(),
+        BlockKind::Final { message_bits: 32 },
+    );
+}
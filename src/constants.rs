@@ -6,6 +6,11 @@ const H: [u32; 8] = [
     0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
 ];
 
+/// SHA224 initial state constants, as 32-bit integers (FIPS 180-4, section 5.3.2).
+const H224: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+
 /// SHA256 round constants, as 32-bit integers.
 const K: [u32; 64] = [
     0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
@@ -23,7 +28,68 @@ pub fn initial_state<F: PrimeField>() -> [[F; 32]; 8] {
     std::array::from_fn(|i| bits_to_field(&to_bits_be::<_, 32>(H[i])))
 }
 
+/// Returns the SHA224 initial hash state, represented as field elements with 32 bits each.
+pub fn initial_state_224<F: PrimeField>() -> [[F; 32]; 8] {
+    std::array::from_fn(|i| bits_to_field(&to_bits_be::<_, 32>(H224[i])))
+}
+
+/// Returns the SHA256 initial hash state as 32-bit integers.
+pub fn initial_state_u32() -> [u32; 8] {
+    H
+}
+
+/// Returns the SHA256 round constants as 32-bit integers.
+pub fn round_constants_u32() -> [u32; 64] {
+    K
+}
+
 /// Returns the SHA256 round constants, represented as field elements with 32 bits each.
 pub fn round_constants<F: PrimeField>() -> [[F; 32]; 64] {
     std::array::from_fn(|i| bits_to_field(&to_bits_be::<_, 32>(K[i])))
 }
+
+/// Tests that the constants decode to the FIPS 180-4 values, derived here from first principles:
+/// `H` holds the first 32 fractional bits of the square roots of the first 8 primes and `K`
+/// those of the cube roots of the first 64 primes (FIPS 180-4, sections 4.2.2 and 5.3.3).
+#[test]
+fn constants_test() {
+    use crate::sha_helpers::bits_to_u32;
+    use kimchi::mina_curves::pasta::Fp;
+
+    // Integer k-th root by binary search: the largest r with r^k <= n.
+    fn iroot(n: u128, k: u32) -> u128 {
+        let (mut lo, mut hi) = (0u128, 1u128 << (128 / k));
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if mid.checked_pow(k).is_some_and(|p| p <= n) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        lo
+    }
+
+    let primes: Vec<u128> = (2u128..)
+        .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .take(64)
+        .collect();
+
+    let state = initial_state::<Fp>();
+    for (i, p) in primes.iter().take(8).enumerate() {
+        let expected = iroot(p << 64, 2) as u32;
+        assert_eq!(bits_to_u32(state[i]), expected, "Mismatch on H[{i}].");
+    }
+
+    let constants = round_constants::<Fp>();
+    for (i, p) in primes.iter().enumerate() {
+        let expected = iroot(p << 96, 3) as u32;
+        assert_eq!(bits_to_u32(constants[i]), expected, "Mismatch on K[{i}].");
+    }
+
+    // Spot checks against the values printed in FIPS 180-4.
+    assert_eq!(bits_to_u32(state[0]), 0x6a09e667, "Mismatch on H[0].");
+    assert_eq!(bits_to_u32(state[7]), 0x5be0cd19, "Mismatch on H[7].");
+    assert_eq!(bits_to_u32(constants[0]), 0x428a2f98, "Mismatch on K[0].");
+    assert_eq!(bits_to_u32(constants[63]), 0xc67178f2, "Mismatch on K[63].");
+}
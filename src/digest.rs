@@ -0,0 +1,107 @@
+use std::hash::{Hash, Hasher};
+
+use ark_ff::PrimeField;
+
+use crate::sha_helpers::*;
+
+/// A SHA256 digest (or chaining state): eight 32-bit big-endian words, one field element per bit.
+pub type Digest<F> = [[F; 32]; 8];
+
+/// A SHA256 digest held as eight 32-bit words of field-element bits.
+/// Equality and hashing use the decoded `u32` words, so digests can be used as map keys.
+#[derive(Clone, Copy, Debug)]
+pub struct Digest256<F: PrimeField>(pub Digest<F>);
+
+impl<F: PrimeField> Digest256<F> {
+    /// Decodes the eight state words into big-endian `u32`s.
+    pub fn words(&self) -> [u32; 8] {
+        std::array::from_fn(|i| bits_to_u32(self.0[i]))
+    }
+}
+
+impl<F: PrimeField> From<Digest<F>> for Digest256<F> {
+    fn from(state: Digest<F>) -> Self {
+        Self(state)
+    }
+}
+
+impl<F: PrimeField> PartialEq for Digest256<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.words() == other.words()
+    }
+}
+
+impl<F: PrimeField> Eq for Digest256<F> {}
+
+/// Compares against a hex string, case-insensitively. Malformed hex compares unequal.
+impl<F: PrimeField> PartialEq<&str> for Digest256<F> {
+    fn eq(&self, other: &&str) -> bool {
+        hex::decode(other).is_ok_and(|bytes| bytes == digest_to_bytes(self.0))
+    }
+}
+
+impl<F: PrimeField> PartialEq<String> for Digest256<F> {
+    fn eq(&self, other: &String) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl<F: PrimeField> Hash for Digest256<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.words().hash(state);
+    }
+}
+
+/// Tests that digests with equal bits are equal map keys.
+#[test]
+fn digest256_test() {
+    use crate::native_sha256::NativeSha256;
+    use kimchi::mina_curves::pasta::Fp;
+    use std::collections::{hash_map::DefaultHasher, HashMap};
+
+    let hash_of = |digest: &Digest256<Fp>| {
+        let mut hasher = DefaultHasher::new();
+        digest.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let first = Digest256::from(NativeSha256::<Fp>::hash_bytes(b"abc"));
+    let second = Digest256::from(NativeSha256::<Fp>::hash_bytes(b"abc"));
+    let other = Digest256::from(NativeSha256::<Fp>::hash_bytes(b"abd"));
+
+    assert_eq!(first, second, "Equal digests compare unequal.");
+    assert_eq!(
+        hash_of(&first),
+        hash_of(&second),
+        "Equal digests hash differently."
+    );
+    assert_ne!(first, other, "Different digests compare equal.");
+
+    let mut seen = HashMap::new();
+    seen.insert(first, "abc");
+    assert_eq!(seen.get(&second), Some(&"abc"), "Digest lookup failed.");
+    assert_eq!(seen.get(&other), None, "Unexpected digest lookup hit.");
+}
+
+/// Tests comparing digests against hex strings.
+#[test]
+fn digest256_hex_eq_test() {
+    use crate::native_sha256::NativeSha256;
+    use kimchi::mina_curves::pasta::Fp;
+
+    let empty = Digest256::from(NativeSha256::<Fp>::hash_bytes(b""));
+    let expected = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    assert_eq!(empty, expected, "Mismatch on lowercase hex.");
+    assert_eq!(empty, expected.to_uppercase(), "Mismatch on uppercase hex.");
+    assert_ne!(
+        empty, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        "Different digest compares equal."
+    );
+
+    // Malformed hex compares unequal instead of panicking.
+    assert_ne!(empty, "", "Empty string compares equal.");
+    assert_ne!(empty, &expected[..63], "Odd-length hex compares equal.");
+    assert_ne!(empty, "zz".repeat(32), "Non-hex string compares equal.");
+    assert_ne!(empty, &expected[..62], "Truncated hex compares equal.");
+}
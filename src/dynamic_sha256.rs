@@ -1,134 +1,218 @@
 #![allow(non_snake_case)]
 
+use std::marker::PhantomData;
+
 use ark_ff::{PrimeField, UniformRand};
 use kimchi::{
     mina_curves::pasta::Fp,
     o1_utils::{tests, FieldHelpers},
 };
-use sha2::{Digest, Sha256};
+use sha2::{Digest as _, Sha256};
 
-use crate::{constants::*, sha_helpers::*};
+use crate::{
+    compression::{Compressor, Sha256Compressor},
+    constants::*,
+    digest::Digest,
+    errors::Sha256Error,
+    sha_helpers::*,
+};
 
 /// Dynamic SHA256 implementation using field elements.
 /// This is used to simulate and test SHA256 logic before building a circuit-compatible version.
-pub struct DynamicSha256<F: PrimeField> {
+///
+/// With the `serde` feature the whole hasher, including its progress, can be serialized between
+/// calls to [`DynamicSha256::compress_next_block`] and resumed in another process. The state is
+/// boolean, so it is stored as its 32 digest bytes.
+///
+/// Blocks are compressed with the [`Compressor`] `C`, the standard SHA256 compression unless
+/// specified.
+pub struct DynamicSha256<F: PrimeField, C: Compressor<F> = Sha256Compressor> {
     padded_preimage: Vec<u8>,
     digest_index: usize,
     state: [[F; 32]; 8],
+    next_block: usize,
+    compressor: PhantomData<C>,
 }
 
 impl<F: PrimeField> DynamicSha256<F> {
     /// Constructor: creates a new SHA256 dynamic hasher from an initial bit vector.
+    /// Panics if the supplied initial state is not boolean or the digest index is invalid; see
+    /// [`DynamicSha256::try_new`].
     pub fn new(
         padded_preimage: Vec<u8>,
         digest_index: usize,
         init_state: Option<[[F; 32]; 8]>,
     ) -> Self {
-        let state = init_state.unwrap_or_else(|| initial_state::<F>());
-
-        Self {
-            padded_preimage,
-            digest_index,
-            state,
-        }
+        Self::try_new(padded_preimage, digest_index, init_state)
+            .unwrap_or_else(|err| panic!("{}", err))
     }
 
-    /// Processes a single 512-bit message chunk, applying SHA256 compression.
-    /// Updates internal state by applying 64 rounds of the SHA256 schedule and mixing.
-    fn process_chunk(&mut self, bits: &[u8], K: [[F; 32]; 64]) {
-        assert_eq!(bits.len(), 512, "Chunk must be 512 bits");
+    /// Fallible constructor: rejects an initial state with entries other than 0 or 1, and a
+    /// `digest_index` that does not mark a length field inside the preimage: it must be
+    /// `512 * k - 64` for some `k >= 1`, with the 64 length bits within `padded_preimage`.
+    pub fn try_new(
+        padded_preimage: Vec<u8>,
+        digest_index: usize,
+        init_state: Option<[[F; 32]; 8]>,
+    ) -> Result<Self, Sha256Error> {
+        Self::try_with_compressor(padded_preimage, digest_index, init_state)
+    }
+}
 
-        // Message schedule W.
-        let field_values = bits_to_field::<F, 512>(&bits);
-        let mut W = [[F::zero(); 32]; 64];
-        for (i, chunk) in field_values.chunks_exact(32).enumerate() {
-            W[i].copy_from_slice(chunk);
+impl<F: PrimeField, C: Compressor<F>> DynamicSha256<F, C> {
+    /// Fallible constructor for a hasher that compresses blocks with `C` instead of the standard
+    /// compression. Validates the initial state like [`DynamicSha256::try_new`].
+    pub fn try_with_compressor(
+        padded_preimage: Vec<u8>,
+        digest_index: usize,
+        init_state: Option<[[F; 32]; 8]>,
+    ) -> Result<Self, Sha256Error> {
+        if digest_index.saturating_add(64) > padded_preimage.len() {
+            return Err(Sha256Error::DigestIndexOutOfRange {
+                digest_index,
+                len: padded_preimage.len(),
+            });
         }
-
-        for i in 16..64 {
-            let s0 = xor(
-                xor(rotate_right(7, W[i - 15]), rotate_right(18, W[i - 15])),
-                right_shift(3, W[i - 15]),
-            );
-            let s1 = xor(
-                xor(rotate_right(17, W[i - 2]), rotate_right(19, W[i - 2])),
-                right_shift(10, W[i - 2]),
-            );
-            W[i] = wrapping_add(wrapping_add(s1, W[i - 7]), wrapping_add(s0, W[i - 16]));
+        if !(digest_index + 64).is_multiple_of(512) {
+            return Err(Sha256Error::DigestIndexMisaligned { digest_index });
         }
 
-        // Compression loop.
-        let (mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h) = (
-            self.state[0],
-            self.state[1],
-            self.state[2],
-            self.state[3],
-            self.state[4],
-            self.state[5],
-            self.state[6],
-            self.state[7],
+        let state = match init_state {
+            Some(state) => {
+                validate_state(&state)?;
+                state
+            }
+            None => initial_state::<F>(),
+        };
+
+        Ok(Self {
+            padded_preimage,
+            digest_index,
+            state,
+            next_block: 0,
+            compressor: PhantomData,
+        })
+    }
+
+    /// Number of blocks of the real message, derived from the digest index.
+    /// The constructors and deserialization validate the index, so the assertion never fires.
+    fn block_count(&self) -> usize {
+        let block_count = (self.digest_index + 64) / 512;
+        assert!(
+            (self.digest_index + 64).is_multiple_of(512)
+                && block_count <= self.padded_preimage.len() / 512,
+            "Digest index does not mark a length field inside the padded preimage!"
         );
+        block_count
+    }
 
-        for i in 0..64 {
-            let S1 = xor(
-                xor(rotate_right(6, e), rotate_right(11, e)),
-                rotate_right(25, e),
-            );
-            let Ch = xor(and(e, f), and(not(e), g));
-            let T1 = wrapping_add(
-                wrapping_add(wrapping_add(wrapping_add(h, S1), Ch), K[i]),
-                W[i],
-            );
+    /// Compresses the next block of the padded preimage into the state. Returns `false`, without
+    /// compressing anything, once every block has been compressed.
+    pub fn compress_next_block(&mut self) -> bool {
+        self.compress_next_block_before(self.padded_preimage.len() / 512)
+    }
 
-            let S0 = xor(
-                xor(rotate_right(2, a), rotate_right(13, a)),
-                rotate_right(22, a),
-            );
-            let Maj = xor(xor(and(a, b), and(a, c)), and(b, c));
-            let T2 = wrapping_add(S0, Maj);
-
-            h = g;
-            g = f;
-            f = e;
-            e = wrapping_add(d, T1);
-            d = c;
-            c = b;
-            b = a;
-            a = wrapping_add(T1, T2);
+    /// [`DynamicSha256::compress_next_block`], stopping before block `end`.
+    fn compress_next_block_before(&mut self, end: usize) -> bool {
+        if self.next_block == 0 {
+            assert_padded_bits(&self.padded_preimage);
+        }
+        if self.next_block >= end {
+            return false;
         }
 
-        // Final state update.
-        self.state[0] = wrapping_add(a, self.state[0]);
-        self.state[1] = wrapping_add(b, self.state[1]);
-        self.state[2] = wrapping_add(c, self.state[2]);
-        self.state[3] = wrapping_add(d, self.state[3]);
-        self.state[4] = wrapping_add(e, self.state[4]);
-        self.state[5] = wrapping_add(f, self.state[5]);
-        self.state[6] = wrapping_add(g, self.state[6]);
-        self.state[7] = wrapping_add(h, self.state[7]);
+        let i = self.next_block;
+        let _span = block_span(i);
+        let chunk = &self.padded_preimage[i * 512..(i + 1) * 512];
+        self.state = C::compress(self.state, chunk, round_constants());
+        self.next_block += 1;
+        true
     }
 
     /// Computes the SHA256 hash over the (already padded) input bitstream.
-    pub fn hash(mut self) -> [[F; 32]; 8] {
+    ///
+    /// **Every block is compressed**, like [`NativeSha256::hash`](crate::native_sha256::NativeSha256::hash),
+    /// including the capacity blocks [`sha256_pad`] appends beyond the minimal padding. Use
+    /// [`DynamicSha256::hash_message_blocks`] for the standard digest of the message.
+    #[must_use]
+    pub fn hash(mut self) -> Digest<F> {
+        while self.compress_next_block() {}
+        trace_digest(self.state);
+
+        // Output digest as [[F; 32]; 8] bit representation.
+        self.state
+    }
+
+    /// Computes the standard SHA256 digest of the message, compressing only its own blocks.
+    ///
+    /// The length field starts at `digest_index`, so the message ends after block
+    /// `(digest_index + 64) / 512`; capacity blocks appended beyond it to fix the circuit size
+    /// are skipped, and the digest does not depend on `max_bits`.
+    /// Panics if [`DynamicSha256::compress_next_block`] already compressed a capacity block.
+    #[must_use]
+    pub fn hash_message_blocks(mut self) -> Digest<F> {
+        let block_count = self.block_count();
         assert!(
-            &self.padded_preimage.len() % 512 == 0,
-            "Input must be padded to 512-bit blocks."
+            self.next_block <= block_count,
+            "Capacity blocks were already compressed!"
         );
+        while self.compress_next_block_before(block_count) {}
+        trace_digest(self.state);
 
-        let K = round_constants();
+        self.state
+    }
+}
 
-        let chunks: Vec<Vec<u8>> = self
-            .padded_preimage
-            .chunks(512)
-            .map(|chunk| chunk.to_vec())
-            .collect();
+/// Serialized form of a [`DynamicSha256`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Snapshot {
+    padded_preimage: Vec<u8>,
+    digest_index: usize,
+    state: [u8; 32],
+    next_block: usize,
+}
 
-        for chunk in chunks {
-            self.process_chunk(&chunk, K);
+#[cfg(feature = "serde")]
+impl<F: PrimeField, C: Compressor<F>> serde::Serialize for DynamicSha256<F, C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Snapshot {
+            padded_preimage: self.padded_preimage.clone(),
+            digest_index: self.digest_index,
+            state: digest_to_bytes(self.state),
+            next_block: self.next_block,
         }
+        .serialize(serializer)
+    }
+}
 
-        // Output digest as [[F; 32]; 8] bit representation.
-        self.state
+#[cfg(feature = "serde")]
+impl<'de, F: PrimeField, C: Compressor<F>> serde::Deserialize<'de> for DynamicSha256<F, C> {
+    /// Rejects snapshots that no hasher could have produced: a preimage that is not whole blocks
+    /// of 0/1 values, a digest index the constructors would refuse, or progress past the last
+    /// block of the preimage.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let snapshot = Snapshot::deserialize(deserializer)?;
+        let padded = &snapshot.padded_preimage;
+        if !padded.len().is_multiple_of(512) || padded.iter().any(|bit| *bit > 1) {
+            return Err(D::Error::custom(
+                "Snapshot preimage is not whole 512-bit blocks of 0/1 values!",
+            ));
+        }
+
+        let mut hasher =
+            Self::try_with_compressor(snapshot.padded_preimage, snapshot.digest_index, None)
+                .map_err(D::Error::custom)?;
+        if snapshot.next_block > hasher.padded_preimage.len() / 512 {
+            return Err(D::Error::custom(
+                "Snapshot progress is past the last block of the preimage!",
+            ));
+        }
+        hasher.state = bytes_to_digest(&snapshot.state);
+        hasher.next_block = snapshot.next_block;
+        Ok(hasher)
     }
 }
 
@@ -137,7 +221,7 @@ impl<F: PrimeField> DynamicSha256<F> {
 fn dynamic_sha256_test() {
     // === Test 1: SHA256 of a zero byte ===
     let zero_bits = from_hex("00");
-    let (padded, digest_index) = sha256_pad(zero_bits, 512);
+    let (padded, digest_index) = sha256_pad(zero_bits, 512).unwrap();
     let zero_hash = DynamicSha256::<Fp>::new(padded, digest_index, None).hash();
     // Output digest as hex string.
     let zero_hash_hex = digest_to_hex(zero_hash);
@@ -157,7 +241,7 @@ fn dynamic_sha256_test() {
     let concatenated = format!("{}{}", first_random_hex, second_random_hex);
 
     let bits = from_hex(&concatenated);
-    let (padded, digest_index) = sha256_pad(bits, 1024);
+    let (padded, digest_index) = sha256_pad(bits, 1024).unwrap();
     let hash_index = 960;
     let dynamic_hash = DynamicSha256::<Fp>::new(padded, digest_index, None).hash();
     // Output digest as hex string.
@@ -193,7 +277,7 @@ fn dynamic_sha256_test() {
         .collect();
 
     let bits = from_hex(&merged_hex);
-    let (padded, digest_index) = sha256_pad(bits, 1536);
+    let (padded, digest_index) = sha256_pad(bits, 1536).unwrap();
     let hash_index = 1472;
     let dynamic_hash = DynamicSha256::<Fp>::new(padded, digest_index, None).hash();
     // Output digest as hex string.
@@ -214,3 +298,177 @@ fn dynamic_sha256_test() {
         "Mismatch between dynamic and standard SHA256."
     );
 }
+
+/// Tests that `hash_message_blocks` skips capacity blocks, which `hash` compresses.
+#[test]
+fn capacity_blocks_test() {
+    let data = b"circuit capacity is not part of the message";
+    let standard = hex::encode(Sha256::digest(data));
+    for max_bits in [512, 2048, 512 * 16] {
+        let (padded, digest_index) = sha256_pad(bytes_to_bits(data), max_bits).unwrap();
+        let hasher = || DynamicSha256::<Fp>::new(padded.clone(), digest_index, None);
+        assert_eq!(
+            digest_to_hex(hasher().hash_message_blocks()),
+            standard,
+            "Mismatch with max_bits = {max_bits}."
+        );
+        assert_eq!(
+            digest_to_hex(hasher().hash()) == standard,
+            max_bits == 512,
+            "Capacity blocks not compressed by hash with max_bits = {max_bits}."
+        );
+    }
+}
+
+/// Tests that a non-boolean initial state is rejected.
+#[test]
+fn init_state_validation_test() {
+    let (padded, digest_index) = sha256_pad(from_hex("00"), 512).unwrap();
+
+    let mut state = initial_state::<Fp>();
+    assert!(
+        DynamicSha256::try_new(padded.clone(), digest_index, Some(state)).is_ok(),
+        "Boolean initial state rejected."
+    );
+
+    state[3][17] = Fp::from(2u8);
+    assert_eq!(
+        DynamicSha256::try_new(padded, digest_index, Some(state)).err(),
+        Some(Sha256Error::NonBooleanState { word: 3, bit: 17 }),
+        "Expected a non-boolean state error."
+    );
+}
+
+/// Tests that a digest index outside the preimage or off a length field position is rejected.
+#[test]
+fn digest_index_validation_test() {
+    let (padded, digest_index) = sha256_pad(from_hex("00"), 1024).unwrap();
+    assert_eq!(digest_index, 448, "Unexpected digest index.");
+
+    // Capacity blocks may follow the length field.
+    for valid in [448, 960] {
+        assert!(
+            DynamicSha256::<Fp>::try_new(padded.clone(), valid, None).is_ok(),
+            "Digest index {valid} rejected."
+        );
+    }
+
+    for (index, len) in [(1472, 1024), (usize::MAX - 63, 1024)] {
+        assert_eq!(
+            DynamicSha256::<Fp>::try_new(padded.clone(), index, None).err(),
+            Some(Sha256Error::DigestIndexOutOfRange {
+                digest_index: index,
+                len
+            }),
+            "Expected an out-of-range error for {index}."
+        );
+    }
+    for index in [0, 447, 449, 512] {
+        assert_eq!(
+            DynamicSha256::<Fp>::try_new(padded.clone(), index, None).err(),
+            Some(Sha256Error::DigestIndexMisaligned {
+                digest_index: index
+            }),
+            "Expected a misalignment error for {index}."
+        );
+    }
+}
+
+/// Differential test: random inputs must hash identically in the native and dynamic engines.
+#[test]
+fn native_dynamic_differential_test() {
+    use crate::native_sha256::NativeSha256;
+
+    let mut rng = tests::make_test_rng(None);
+    // Lengths around the padding boundaries (55/56 bytes) and block boundaries (64 bytes).
+    for len in [0usize, 1, 31, 55, 56, 63, 64, 65, 119, 120, 128, 200] {
+        let bytes: Vec<u8> = (0..len.div_ceil(32))
+            .flat_map(|_| Fp::rand(&mut rng).to_bytes())
+            .take(len)
+            .collect();
+        let bits = bytes_to_bits(&bytes);
+
+        // Minimal padding and padding with extra circuit capacity.
+        for extra_blocks in [0, 2] {
+            let max_bits = min_padded_bits(bits.len()) + 512 * extra_blocks;
+            let (padded, digest_index) = sha256_pad(bits.clone(), max_bits).unwrap();
+
+            assert_eq!(
+                NativeSha256::<Fp>::new(padded.clone()).hash(),
+                DynamicSha256::<Fp>::new(padded, digest_index, None).hash(),
+                "Engines diverge on {len} bytes with {extra_blocks} extra blocks."
+            );
+        }
+    }
+}
+
+/// Tests that a hasher snapshotted mid-hash resumes to the uninterrupted digest.
+#[cfg(feature = "serde")]
+#[test]
+fn serde_snapshot_test() {
+    let data = [0x7e; 200];
+    let (padded, digest_index) = sha256_pad(bytes_to_bits(&data), 2048).unwrap();
+    let uninterrupted = DynamicSha256::<Fp>::new(padded.clone(), digest_index, None).hash();
+
+    let mut hasher = DynamicSha256::<Fp>::new(padded, digest_index, None);
+    assert!(hasher.compress_next_block(), "First block not compressed.");
+    assert!(hasher.compress_next_block(), "Second block not compressed.");
+    let json = serde_json::to_string(&hasher).unwrap();
+
+    let resumed: DynamicSha256<Fp> = serde_json::from_str(&json).unwrap();
+    assert_eq!(resumed.next_block, 2, "Progress lost in serialization.");
+    assert_eq!(
+        resumed.hash(),
+        uninterrupted,
+        "Resumed hash differs from the uninterrupted one."
+    );
+    assert_eq!(
+        digest_to_hex(uninterrupted),
+        hex::encode(Sha256::digest(data)),
+        "Mismatch with standard SHA256."
+    );
+}
+
+/// Tests that tampered snapshots are rejected on deserialization instead of panicking later.
+#[cfg(feature = "serde")]
+#[test]
+fn serde_snapshot_tampered_test() {
+    let (padded, digest_index) = sha256_pad(bytes_to_bits(&[0x7e; 10]), 2048).unwrap();
+    let hasher = DynamicSha256::<Fp>::new(padded, digest_index, None);
+    let snapshot: serde_json::Value = serde_json::to_value(&hasher).unwrap();
+
+    let tamper = |field: &str, value: serde_json::Value| {
+        let mut snapshot = snapshot.clone();
+        snapshot[field] = value;
+        serde_json::from_value::<DynamicSha256<Fp>>(snapshot)
+    };
+    assert!(
+        tamper("next_block", 0.into()).is_ok(),
+        "Untampered snapshot rejected."
+    );
+    assert!(
+        tamper("next_block", 5.into()).is_err(),
+        "Progress past the preimage accepted."
+    );
+    assert!(
+        tamper("digest_index", 500.into()).is_err(),
+        "Misaligned digest index accepted."
+    );
+    assert!(
+        tamper("digest_index", 2496.into()).is_err(),
+        "Out-of-range digest index accepted."
+    );
+
+    let mut bytes = snapshot["padded_preimage"].clone();
+    bytes[0] = 0xff.into();
+    assert!(
+        tamper("padded_preimage", bytes).is_err(),
+        "Non-bit preimage accepted."
+    );
+    let mut short = snapshot["padded_preimage"].clone();
+    short.as_array_mut().unwrap().pop();
+    assert!(
+        tamper("padded_preimage", short).is_err(),
+        "Unaligned preimage accepted."
+    );
+}
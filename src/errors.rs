@@ -0,0 +1,137 @@
+use std::fmt;
+
+/// Errors returned while padding a bit-level SHA256 preimage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PadError {
+    /// The padded message needs more bits than the requested `max_bits`.
+    /// `required_bits` saturates at `usize::MAX` when the real count does not fit.
+    InputTooLong {
+        required_bits: usize,
+        max_bits: usize,
+    },
+    /// `max_bits` is not a multiple of the 512-bit block size.
+    MaxBitsNotBlockAligned { got: usize },
+    /// `max_bits` is below one 512-bit block, the padded size of even the empty message.
+    MaxBitsBelowOneBlock { got: usize },
+    /// A block count whose size in bits does not fit in `usize`.
+    TooManyBlocks { num_blocks: usize },
+    /// A byte capacity whose size in bits does not fit in `usize`.
+    TooManyBytes { max_bytes: usize },
+    /// The message length, in bits, does not fit SHA256's 64-bit length field.
+    MessageTooLong { bit_length: u128 },
+    /// A padded buffer's length is not a multiple of 512 bits.
+    BufferNotBlockAligned { len: usize },
+    /// A padded buffer holds a value other than 0 or 1.
+    NonBitValue { index: usize },
+    /// No block ends in a length field consistent with the `1` marker and zero fill.
+    InvalidPadding,
+}
+
+impl fmt::Display for PadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PadError::InputTooLong {
+                required_bits,
+                max_bits,
+            } => write!(
+                f,
+                "Padded message needs {} bits but max_bits is {}!",
+                required_bits, max_bits
+            ),
+            PadError::MaxBitsNotBlockAligned { got } => {
+                write!(f, "max_bits must be a multiple of 512 but got {}!", got)
+            }
+            PadError::MaxBitsBelowOneBlock { got } => write!(
+                f,
+                "max_bits must be at least 512, one block even for the empty message, but got {}!",
+                got
+            ),
+            PadError::TooManyBlocks { num_blocks } => {
+                write!(f, "{} blocks of 512 bits overflow usize!", num_blocks)
+            }
+            PadError::TooManyBytes { max_bytes } => {
+                write!(f, "{} bytes of 8 bits overflow usize!", max_bytes)
+            }
+            PadError::MessageTooLong { bit_length } => write!(
+                f,
+                "Message is {} bits long but SHA256 supports less than 2^64 bits!",
+                bit_length
+            ),
+            PadError::BufferNotBlockAligned { len } => write!(
+                f,
+                "Padded buffer must be a multiple of 512 bits long but is {} bits!",
+                len
+            ),
+            PadError::NonBitValue { index } => {
+                write!(f, "Padded buffer has a non-bit value at index {}!", index)
+            }
+            PadError::InvalidPadding => write!(
+                f,
+                "Padded buffer has no length field matching its marker and zero fill!"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PadError {}
+
+/// Errors returned by the hashers and their entry points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sha256Error {
+    /// The input is longer than the configured byte limit.
+    InputTooLong { len: usize, max_bytes: usize },
+    /// A supplied state word contains a field element that is neither 0 nor 1.
+    NonBooleanState { word: usize, bit: usize },
+    /// The requested number of compression rounds is outside `1..=64`.
+    InvalidRounds { rounds: usize },
+    /// A precomputed prefix is not a whole number of 64-byte blocks.
+    PrefixNotBlockAligned { len: usize },
+    /// The accelerated oracle and the field engine disagree on a selftest vector.
+    SelftestMismatch { vector: usize },
+    /// The digest index leaves no room for the 64-bit length field inside the padded preimage.
+    DigestIndexOutOfRange { digest_index: usize, len: usize },
+    /// The digest index is not 64 bits before a block boundary, so it cannot mark a length field.
+    DigestIndexMisaligned { digest_index: usize },
+}
+
+impl fmt::Display for Sha256Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sha256Error::InputTooLong { len, max_bytes } => write!(
+                f,
+                "Input is {} bytes long but at most {} bytes are allowed!",
+                len, max_bytes
+            ),
+            Sha256Error::NonBooleanState { word, bit } => write!(
+                f,
+                "State word {} has a non-boolean value at bit {}!",
+                word, bit
+            ),
+            Sha256Error::InvalidRounds { rounds } => {
+                write!(f, "Rounds must be between 1 and 64 but got {}!", rounds)
+            }
+            Sha256Error::PrefixNotBlockAligned { len } => write!(
+                f,
+                "Prefix must be a multiple of 64 bytes long but is {} bytes!",
+                len
+            ),
+            Sha256Error::SelftestMismatch { vector } => write!(
+                f,
+                "Accelerated and field digests differ on selftest vector {}!",
+                vector
+            ),
+            Sha256Error::DigestIndexOutOfRange { digest_index, len } => write!(
+                f,
+                "Digest index {} leaves no room for the length field in a {}-bit preimage!",
+                digest_index, len
+            ),
+            Sha256Error::DigestIndexMisaligned { digest_index } => write!(
+                f,
+                "Digest index must be a multiple of 512 minus 64 but got {}!",
+                digest_index
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Sha256Error {}
@@ -0,0 +1,855 @@
+use std::{
+    hash::Hasher,
+    io::{self, Write},
+};
+
+use ark_ff::{BigInteger, PrimeField};
+use kimchi::{mina_curves::pasta::Fp, o1_utils::FieldHelpers};
+
+use crate::{
+    compression::compress_block,
+    constants::{initial_state, round_constants},
+    digest::Digest,
+    dynamic_sha256::DynamicSha256,
+    errors::PadError,
+    native_sha256::{NativeSha256, StreamingSha256},
+    sha_helpers::{
+        bits_to_u32, bytes_to_bits, constant_time_eq, digest_to_bytes, digest_to_hex, from_hex,
+        pad_errors, padded_bits, sha256_pad, to_bits_be,
+    },
+};
+
+/// Serializes a field element to its canonical big-endian bytes.
+/// The width is fixed to `8 * F::BigInt` limbs (32 bytes for Pasta and other 4-limb fields),
+/// so leading zero bytes are always kept. This is the byte reversal of `FieldHelpers::to_bytes`,
+/// which uses little-endian order.
+#[must_use]
+pub fn field_to_bytes_be<F: PrimeField>(x: F) -> Vec<u8> {
+    x.into_bigint().to_bytes_be()
+}
+
+/// Hashes a sequence of field elements by concatenating their canonical big-endian
+/// serializations (see [`field_to_bytes_be`]) in order.
+#[must_use]
+pub fn hash_fields<F: PrimeField>(elems: &[F]) -> Digest<F> {
+    let mut hasher = StreamingSha256::<F>::new();
+    for elem in elems {
+        hasher.update(&field_to_bytes_be(*elem));
+    }
+    hasher.finalize()
+}
+
+/// Hashes the canonical little-endian serialization of a field element, the same bytes as
+/// `FieldHelpers::to_bytes`: `8 * F::BigInt` limbs wide (32 bytes for Pasta), least significant
+/// byte first. This is the byte reversal of the [`field_to_bytes_be`] preimage of
+/// [`hash_fields`], so the two digests differ.
+#[must_use]
+pub fn hash_field_le<F: PrimeField>(x: F) -> Digest<F> {
+    NativeSha256::hash_bytes(&x.into_bigint().to_bytes_le())
+}
+
+/// Concatenates the `FieldHelpers::to_hex` encoding of each element, in order.
+/// Each element contributes `2 * F::size_in_bytes()` hex characters (64 for Pasta), in the
+/// same byte order as `FieldHelpers::to_bytes`.
+#[must_use]
+pub fn fields_to_hex<F: PrimeField>(elems: &[F]) -> String {
+    elems.iter().map(|elem| elem.to_hex()).collect()
+}
+
+/// Decodes a hex string into bytes and hashes them.
+#[must_use]
+pub fn hash_hex<F: PrimeField>(hex: &str) -> Digest<F> {
+    let bytes = hex::decode(hex).expect("Invalid hex.");
+    NativeSha256::hash_bytes(&bytes)
+}
+
+/// Hashes `tag0 || seg0 || tag1 || seg1 || ...` for the given `(tag, segment)` pairs, in order.
+/// The pieces are streamed without building the interleaved buffer. No lengths are added, so
+/// the tags must make the encoding unambiguous for the caller's format.
+#[must_use]
+pub fn hash_tagged_segments<F: PrimeField>(pairs: &[(&[u8], &[u8])]) -> Digest<F> {
+    let mut hasher = StreamingSha256::<F>::new();
+    for (tag, segment) in pairs {
+        hasher.update(tag);
+        hasher.update(segment);
+    }
+    hasher.finalize()
+}
+
+/// Computes the BIP-340 tagged hash `SHA256(SHA256(tag) || SHA256(tag) || msg)`, as used for
+/// Schnorr challenges, nonces and auxiliary randomness in Taproot.
+#[must_use]
+pub fn tagged_hash<F: PrimeField>(tag: &str, msg: &[u8]) -> Digest<F> {
+    let tag_hash = digest_to_bytes(NativeSha256::<F>::hash_bytes(tag.as_bytes()));
+    let mut hasher = StreamingSha256::<F>::new();
+    hasher.update(&tag_hash);
+    hasher.update(&tag_hash);
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// Streaming hasher for structured records of named fields.
+///
+/// Each field is absorbed, in insertion order, as
+/// `tag_len || tag || value_len || value`, where both lengths are the byte counts as 8-byte
+/// big-endian integers and `tag` is UTF-8. The digest is SHA256 over the concatenation of all
+/// fields. Length prefixes make the encoding injective: no reordering of fields or shift of bytes
+/// across a field boundary gives the same byte stream.
+#[derive(Clone, Default)]
+pub struct RecordHasher<F: PrimeField> {
+    inner: StreamingSha256<F>,
+}
+
+impl<F: PrimeField> RecordHasher<F> {
+    /// Creates a hasher for an empty record.
+    pub fn new() -> Self {
+        Self {
+            inner: StreamingSha256::new(),
+        }
+    }
+
+    /// Absorbs one field.
+    pub fn add_field(&mut self, tag: &str, value: &[u8]) -> &mut Self {
+        self.inner.update(&(tag.len() as u64).to_be_bytes());
+        self.inner.update(tag.as_bytes());
+        self.inner.update(&(value.len() as u64).to_be_bytes());
+        self.inner.update(value);
+        self
+    }
+
+    /// Returns the digest of the record.
+    #[must_use]
+    pub fn finalize(self) -> Digest<F> {
+        self.inner.finalize()
+    }
+}
+
+/// Outcome of [`hash_checked`]: every problem found with the request, and the digest when there
+/// was none.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashReport<F: PrimeField> {
+    /// Blocks the message needs with minimal padding, reported even when hashing failed.
+    pub required_blocks: usize,
+    /// All padding and length problems, in the order they were checked.
+    pub errors: Vec<PadError>,
+    /// The standard digest of `data`, present only when `errors` is empty.
+    pub digest: Option<Digest<F>>,
+}
+
+impl<F: PrimeField> HashReport<F> {
+    /// Whether the data was hashed without problems.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Pads `data` to `max_bits` and hashes it without panicking or stopping at the first problem,
+/// for batch validation that reports everything at once. Unlike [`sha256_pad`], every check of
+/// [`pad_errors`] is reported, so e.g. a misaligned and too small `max_bits` reports both.
+/// Capacity blocks beyond the message do not change the digest, see
+/// [`DynamicSha256::hash_message_blocks`].
+#[must_use]
+pub fn hash_checked<F: PrimeField>(data: &[u8], max_bits: usize) -> HashReport<F> {
+    // Counted in `u128`: from 512 MiB on, the bit length overflows a 32-bit `usize`.
+    let bit_length = data.len() as u128 * 8;
+    let errors = pad_errors(bit_length, max_bits);
+
+    // `sha256_pad` runs the same checks, so it cannot fail once `pad_errors` found none.
+    let digest = errors.is_empty().then(|| {
+        let (padded, digest_index) = sha256_pad(bytes_to_bits(data), max_bits)
+            .expect("pad_errors accepted these parameters");
+        DynamicSha256::new(padded, digest_index, None).hash_message_blocks()
+    });
+
+    HashReport {
+        required_blocks: usize::try_from(padded_bits(bit_length) / 512).unwrap_or(usize::MAX),
+        errors,
+        digest,
+    }
+}
+
+/// Computes the hash chain `H^n(seed)`, as used by one-time-password schemes: the seed is hashed
+/// once, then each 32-byte digest is hashed again, `n` times in total.
+/// Panics if `n` is 0, since `H^0(seed)` is the seed itself rather than a digest.
+#[must_use]
+pub fn hash_chain<F: PrimeField>(seed: &[u8], n: usize) -> Digest<F> {
+    assert!(n > 0, "A hash chain needs at least one step!");
+    (1..n).fold(NativeSha256::hash_bytes(seed), |digest, _| {
+        NativeSha256::hash_bytes(&digest_to_bytes(digest))
+    })
+}
+
+/// Checks in constant time that `next` is one hash chain step after `prev`, i.e. that
+/// `next = SHA256(prev)` over the 32 digest bytes.
+#[must_use]
+pub fn verify_chain_step<F: PrimeField>(prev: Digest<F>, next: Digest<F>) -> bool {
+    let expected = NativeSha256::<F>::hash_bytes(&digest_to_bytes(prev));
+    constant_time_eq(&digest_to_bytes(expected), &digest_to_bytes(next))
+}
+
+/// Computes `SHA256(prev || counter)` for KDF and DRBG loops. The preimage is 288 bits: the
+/// 256 digest bits, word by word, followed by the 32 bits of the big-endian counter. That fits
+/// in a single block, which is laid out directly on the stack and compressed once, so no
+/// allocation happens.
+#[must_use]
+pub fn hash_digest_counter<F: PrimeField>(prev: Digest<F>, counter: u32) -> Digest<F> {
+    let mut block = [0u8; 512];
+    for (bit, value) in block.iter_mut().zip(prev.iter().flatten()) {
+        *bit = u8::from(value.is_one());
+    }
+    block[256..288].copy_from_slice(&to_bits_be::<_, 32>(counter));
+    block[288] = 1;
+    block[448..].copy_from_slice(&to_bits_be::<_, 64>(288u64));
+    compress_block(initial_state(), &block, round_constants())
+}
+
+/// Hashes every message and compares the digest with its expected value in constant time.
+/// The result is aligned with `pairs` by index. With the `parallel` feature the pairs are
+/// checked on the rayon thread pool, except on wasm32, which has no threads.
+#[must_use]
+pub fn verify_batch<F: PrimeField>(pairs: &[(&[u8], [u8; 32])]) -> Vec<bool> {
+    let verify = |(message, expected): &(&[u8], [u8; 32])| {
+        constant_time_eq(
+            &digest_to_bytes(NativeSha256::<F>::hash_bytes(message)),
+            expected,
+        )
+    };
+
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    {
+        use rayon::prelude::*;
+        pairs.par_iter().map(verify).collect()
+    }
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    pairs.iter().map(verify).collect()
+}
+
+/// Startup sanity gate: hashes the empty message and `"abc"` and compares them with their FIPS
+/// 180-4 digests. The vectors go through both engines and the big-endian conversions
+/// (`from_hex`, `to_bits_be` in the length field, `bits_to_u32`), so an endianness regression
+/// in any of them returns `false`. Costs two block compressions per engine.
+#[must_use]
+pub fn self_check<F: PrimeField>() -> bool {
+    const EMPTY: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    let dynamic = |hex: &str| {
+        sha256_pad(from_hex(hex), 512)
+            .ok()
+            .map(|(padded, digest_index)| {
+                DynamicSha256::<F>::new(padded, digest_index, None).hash()
+            })
+    };
+    let (Some(empty_dynamic), Some(abc_dynamic)) = (dynamic(""), dynamic("616263")) else {
+        return false;
+    };
+    let empty_native = NativeSha256::<F>::hash_bytes(b"");
+    let abc_native = NativeSha256::<F>::hash_bytes(b"abc");
+
+    digest_to_hex(empty_native) == EMPTY
+        && digest_to_hex(empty_dynamic) == EMPTY
+        && digest_to_hex(abc_native) == ABC
+        && digest_to_hex(abc_dynamic) == ABC
+        && bits_to_u32(abc_native[0]) == 0xba78_16bf
+}
+
+/// Computes the SHA256 digest bytes of any supported input, see [`HashInput`].
+#[must_use]
+pub fn sha256<I: HashInput>(input: I) -> [u8; 32] {
+    input.digest_bytes()
+}
+
+/// Inputs accepted by [`sha256`]: `&[u8]`, `Vec<u8>`, `&str` (its UTF-8 bytes) and
+/// [`FieldElems`]. The trait is sealed so the set of encodings stays fixed.
+pub trait HashInput: sealed::Sealed {}
+
+/// Field elements for [`sha256`], serialized like [`hash_fields`]: each element's canonical
+/// big-endian bytes (see [`field_to_bytes_be`]), concatenated in order.
+/// A wrapper rather than `&[F]` itself, since an impl for every `&[F]` would overlap with the one
+/// for `&[u8]`.
+#[derive(Clone, Copy, Debug)]
+pub struct FieldElems<'a, F: PrimeField>(pub &'a [F]);
+
+mod sealed {
+    pub trait Sealed {
+        fn digest_bytes(self) -> [u8; 32];
+    }
+}
+
+impl HashInput for &[u8] {}
+impl sealed::Sealed for &[u8] {
+    fn digest_bytes(self) -> [u8; 32] {
+        digest_to_bytes(NativeSha256::<Fp>::hash_bytes(self))
+    }
+}
+
+impl HashInput for Vec<u8> {}
+impl sealed::Sealed for Vec<u8> {
+    fn digest_bytes(self) -> [u8; 32] {
+        self.as_slice().digest_bytes()
+    }
+}
+
+impl HashInput for &str {}
+impl sealed::Sealed for &str {
+    fn digest_bytes(self) -> [u8; 32] {
+        self.as_bytes().digest_bytes()
+    }
+}
+
+impl<F: PrimeField> HashInput for FieldElems<'_, F> {}
+impl<F: PrimeField> sealed::Sealed for FieldElems<'_, F> {
+    fn digest_bytes(self) -> [u8; 32] {
+        digest_to_bytes(hash_fields(self.0))
+    }
+}
+
+/// Adapter plugging SHA256 into code generic over [`std::hash::Hasher`], e.g. for hashing that
+/// is deterministic across runs and platforms.
+///
+/// `write` feeds the bytes to a [`StreamingSha256`]; `finish` hashes everything written so
+/// far and, because the trait returns a `u64`, truncates the digest to its low 64 bits (the last
+/// 8 digest bytes, read big-endian). The truncated value carries no cryptographic guarantees.
+///
+/// Integers are written as little-endian bytes, with `usize` and `isize` widened to 64 bits,
+/// so the same `Hash` impl gives the same value on every target.
+#[derive(Clone, Default)]
+pub struct Sha256Hasher<F: PrimeField> {
+    inner: StreamingSha256<F>,
+}
+
+impl<F: PrimeField> Hasher for Sha256Hasher<F> {
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    fn write_u16(&mut self, x: u16) {
+        self.write(&x.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, x: u32) {
+        self.write(&x.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, x: u64) {
+        self.write(&x.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, x: u128) {
+        self.write(&x.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, x: usize) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_isize(&mut self, x: isize) {
+        self.write_u64(x as i64 as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        let bytes = digest_to_bytes(self.inner.clone().finalize());
+        u64::from_be_bytes(bytes[24..].try_into().unwrap())
+    }
+}
+
+/// Tee writer: forwards every write to an inner writer and hashes the bytes it accepted, so data
+/// streamed to a file or socket is hashed without a second pass.
+///
+/// Only the bytes the inner writer reports as written are hashed, so after short writes the
+/// digest still covers exactly what reached it.
+pub struct HashingWriter<F: PrimeField, W: Write> {
+    inner: W,
+    hasher: StreamingSha256<F>,
+}
+
+impl<F: PrimeField, W: Write> HashingWriter<F, W> {
+    /// Wraps `inner` with an empty streaming hasher.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: StreamingSha256::new(),
+        }
+    }
+
+    /// Returns the inner writer and the digest of everything written through it.
+    #[must_use]
+    pub fn finish(self) -> (W, Digest<F>) {
+        (self.inner, self.hasher.finalize())
+    }
+}
+
+impl<F: PrimeField, W: Write> Write for HashingWriter<F, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Tests field element hashing against Rust's standard `sha2` implementation.
+#[test]
+fn hash_fields_test() {
+    use crate::sha_helpers::digest_to_hex;
+    use ark_ff::UniformRand;
+    use kimchi::{mina_curves::pasta::Fp, o1_utils::tests};
+    use sha2::{Digest, Sha256};
+
+    let mut rng = tests::make_test_rng(None);
+    let elems = [Fp::rand(&mut rng), Fp::rand(&mut rng), Fp::rand(&mut rng)];
+
+    // === Test 1: Serialization is the big-endian form of `o1_utils` bytes ===
+    for elem in elems {
+        let mut le_bytes = elem.to_bytes();
+        le_bytes.reverse();
+        assert_eq!(field_to_bytes_be(elem), le_bytes, "Serialization mismatch.");
+        assert_eq!(
+            field_to_bytes_be(elem).len(),
+            32,
+            "Serialization width mismatch."
+        );
+    }
+
+    // === Test 2: Digest matches standard Sha256 over the concatenation ===
+    let bytes: Vec<u8> = elems.iter().flat_map(|e| field_to_bytes_be(*e)).collect();
+    let std_hash_hex = hex::encode(Sha256::digest(&bytes));
+    assert_eq!(
+        digest_to_hex(hash_fields(&elems)),
+        std_hash_hex,
+        "Mismatch between field and standard SHA256."
+    );
+
+    // === Test 3: Changing one element changes the digest ===
+    let mut changed = elems;
+    changed[1] += Fp::from(1u8);
+    assert_ne!(
+        digest_to_hex(hash_fields(&changed)),
+        std_hash_hex,
+        "Changing an element did not change the digest."
+    );
+}
+
+/// Tests `fields_to_hex` and `hash_hex` reproduce the manual hex convention of the hasher tests.
+#[test]
+fn fields_to_hex_test() {
+    use crate::sha_helpers::digest_to_hex;
+    use ark_ff::UniformRand;
+    use kimchi::{mina_curves::pasta::Fp, o1_utils::tests};
+    use sha2::{Digest, Sha256};
+
+    let mut rng = tests::make_test_rng(None);
+    let first_random = Fp::rand(&mut rng);
+    let second_random = Fp::rand(&mut rng);
+    let concatenated = format!("{}{}", first_random.to_hex(), second_random.to_hex());
+
+    let hex = fields_to_hex(&[first_random, second_random]);
+    assert_eq!(hex, concatenated, "Mismatch with manual concatenation.");
+    assert_eq!(hex.len(), 128, "Mismatch on hex width.");
+
+    let bytes = hex::decode(&concatenated).unwrap();
+    assert_eq!(
+        digest_to_hex(hash_hex::<Fp>(&hex)),
+        hex::encode(Sha256::digest(&bytes)),
+        "Mismatch between hex and standard SHA256."
+    );
+}
+
+/// Tests that `Sha256Hasher` returns the low 64 bits of the digest of the written bytes.
+#[test]
+fn sha256_hasher_test() {
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    let finish = |parts: &[&[u8]]| {
+        let mut hasher = Sha256Hasher::<Fp>::default();
+        parts.iter().for_each(|part| hasher.write(part));
+        hasher.finish()
+    };
+
+    let expected = Sha256::digest(b"hello world");
+    assert_eq!(
+        finish(&[b"hello ", b"world"]),
+        u64::from_be_bytes(expected[24..].try_into().unwrap()),
+        "Mismatch with the low 64 bits of SHA256."
+    );
+    assert_eq!(
+        finish(&[b"hello world"]),
+        finish(&[b"hello", b" world"]),
+        "Equal byte sequences must agree."
+    );
+    assert_ne!(
+        finish(&[b"hello world"]),
+        finish(&[b"hello World"]),
+        "Changed input must change the value."
+    );
+
+    // Integer writes must feed the same bytes on every target.
+    let mut hasher = Sha256Hasher::<Fp>::default();
+    hasher.write_u16(0xbeef);
+    hasher.write_u32(0x0102_0304);
+    hasher.write_usize(5);
+    hasher.write_isize(-2);
+    assert_eq!(
+        hasher.finish(),
+        finish(&[
+            &[0xef, 0xbe],
+            &[0x04, 0x03, 0x02, 0x01],
+            &[5, 0, 0, 0, 0, 0, 0, 0],
+            &[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        ]),
+        "Integers must be written as little-endian bytes, with usize and isize as 64 bits."
+    );
+}
+
+/// Tests tagged segment hashing against the manually interleaved bytes.
+#[test]
+fn hash_tagged_segments_test() {
+    use crate::sha_helpers::digest_to_hex;
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    let segment = [0xabu8; 70];
+    let pairs: [(&[u8], &[u8]); 3] = [(b"\x00", b"key"), (b"\x01", &segment), (b"\x02", b"")];
+    let interleaved: Vec<u8> = pairs
+        .iter()
+        .flat_map(|(tag, segment)| [*tag, *segment].concat())
+        .collect();
+
+    assert_eq!(
+        digest_to_hex(hash_tagged_segments::<Fp>(&pairs)),
+        hex::encode(Sha256::digest(&interleaved)),
+        "Mismatch between tagged segments and interleaved bytes."
+    );
+    assert_eq!(
+        digest_to_hex(hash_tagged_segments::<Fp>(&[])),
+        hex::encode(Sha256::digest(b"")),
+        "No segments must hash the empty message."
+    );
+}
+
+/// Tests BIP-340 tagged hashes against known tag digests and the standard construction.
+#[test]
+fn tagged_hash_test() {
+    use crate::sha_helpers::digest_to_hex;
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    // === Test 1: Tag digests of the BIP-340 tags ===
+    for (tag, expected) in [
+        (
+            "BIP0340/challenge",
+            "7bb52d7a9fef58323eb1bf7a407db382d2f3f2d81bb1224f49fe518f6d48d37c",
+        ),
+        (
+            "BIP0340/aux",
+            "f1ef4e5ec063cada6d94cafa9d987ea069265839ecc11f972d77a52ed8c1cc90",
+        ),
+        (
+            "BIP0340/nonce",
+            "07497734a79bcb355b9b8c7d034f121cf434d73ef72dda19870061fb52bfeb2f",
+        ),
+    ] {
+        assert_eq!(
+            digest_to_hex(NativeSha256::<Fp>::hash_bytes(tag.as_bytes())),
+            expected,
+            "Mismatch on the digest of tag {tag}."
+        );
+    }
+
+    // === Test 2: Challenge over a zero `R || P || m` ===
+    assert_eq!(
+        digest_to_hex(tagged_hash::<Fp>("BIP0340/challenge", &[0u8; 96])),
+        "9b44352d31b10c501fa0ae1ba915d94e8c4cfbb10cd92017468ca4d107a12d2d",
+        "Mismatch on the zero challenge."
+    );
+
+    // === Test 3: Matches the construction over standard SHA256 ===
+    let msg = b"tagged message";
+    let tag_hash = Sha256::digest(b"BIP0340/aux");
+    assert_eq!(
+        digest_to_hex(tagged_hash::<Fp>("BIP0340/aux", msg)),
+        hex::encode(Sha256::digest([&tag_hash[..], &tag_hash[..], msg].concat())),
+        "Mismatch with the standard tagged hash."
+    );
+}
+
+/// Tests every `sha256` input type against standard SHA256.
+#[test]
+fn sha256_input_test() {
+    use ark_ff::UniformRand;
+    use kimchi::o1_utils::tests;
+    use sha2::{Digest, Sha256};
+
+    let expected: [u8; 32] = Sha256::digest(b"abc").into();
+    assert_eq!(sha256(&b"abc"[..]), expected, "Mismatch on &[u8].");
+    assert_eq!(sha256(b"abc".to_vec()), expected, "Mismatch on Vec<u8>.");
+    assert_eq!(sha256("abc"), expected, "Mismatch on &str.");
+
+    let text = "héllo, wörld";
+    assert_eq!(
+        sha256(text),
+        sha256(text.as_bytes()),
+        "&str must hash its UTF-8 bytes."
+    );
+
+    let mut rng = tests::make_test_rng(None);
+    let elems = [Fp::rand(&mut rng), Fp::rand(&mut rng)];
+    let bytes: Vec<u8> = elems.iter().flat_map(|e| field_to_bytes_be(*e)).collect();
+    assert_eq!(
+        sha256(FieldElems(&elems)),
+        <[u8; 32]>::from(Sha256::digest(&bytes)),
+        "Mismatch on field elements."
+    );
+}
+
+/// Tests hash chains against repeated hashing and step verification.
+#[test]
+fn hash_chain_test() {
+    use sha2::{Digest, Sha256};
+
+    let seed = b"one-time password seed";
+    let h1 = Sha256::digest(seed);
+    let h2 = Sha256::digest(h1);
+    let h3 = Sha256::digest(h2);
+    assert_eq!(
+        digest_to_bytes(hash_chain::<Fp>(seed, 3)),
+        <[u8; 32]>::from(h3),
+        "Mismatch with three manual hashes."
+    );
+    assert_eq!(
+        digest_to_bytes(hash_chain::<Fp>(seed, 1)),
+        <[u8; 32]>::from(h1),
+        "One step must be a single hash."
+    );
+
+    let (second, third) = (hash_chain::<Fp>(seed, 2), hash_chain::<Fp>(seed, 3));
+    assert!(verify_chain_step(second, third), "Valid step rejected.");
+    assert!(!verify_chain_step(third, second), "Reversed step accepted.");
+
+    let mut tampered = second;
+    tampered[7][31] = Fp::from(1u8) - tampered[7][31];
+    assert!(
+        !verify_chain_step(tampered, third),
+        "Tampered intermediate accepted."
+    );
+}
+
+/// Tests that `hash_checked` collects every problem and still reports the block count.
+#[test]
+fn hash_checked_test() {
+    use sha2::{Digest, Sha256};
+
+    let data = [0x41u8; 100];
+
+    // === Test 1: max_bits too small ===
+    let report = hash_checked::<Fp>(&data, 512);
+    assert!(!report.is_ok(), "Undersized max_bits accepted.");
+    assert_eq!(report.required_blocks, 2, "Mismatch on required blocks.");
+    assert_eq!(
+        report.errors,
+        vec![PadError::InputTooLong {
+            required_bits: 1024,
+            max_bits: 512
+        }],
+        "Mismatch on collected errors."
+    );
+    assert_eq!(report.digest, None, "Unexpected digest.");
+
+    // === Test 2: Every failed check is reported ===
+    let report = hash_checked::<Fp>(&data, 300);
+    assert_eq!(
+        report.errors,
+        vec![
+            PadError::MaxBitsBelowOneBlock { got: 300 },
+            PadError::MaxBitsNotBlockAligned { got: 300 },
+            PadError::InputTooLong {
+                required_bits: 1024,
+                max_bits: 300
+            },
+        ],
+        "Mismatch on collected errors."
+    );
+
+    // === Test 3: Success with spare capacity ===
+    let report = hash_checked::<Fp>(&data, 4096);
+    assert!(report.is_ok(), "Valid request rejected.");
+    assert_eq!(
+        report.digest.map(digest_to_bytes),
+        Some(<[u8; 32]>::from(Sha256::digest(data))),
+        "Mismatch with standard SHA256."
+    );
+}
+
+/// Tests the record encoding and that field order and boundaries are bound by the digest.
+#[test]
+fn record_hasher_test() {
+    use sha2::{Digest, Sha256};
+
+    let record = |fields: &[(&str, &[u8])]| {
+        let mut hasher = RecordHasher::<Fp>::new();
+        for (tag, value) in fields {
+            hasher.add_field(tag, value);
+        }
+        digest_to_bytes(hasher.finalize())
+    };
+
+    // === Test 1: Exact encoding ===
+    let mut encoding = Vec::new();
+    for (tag, value) in [("name", &b"alice"[..]), ("id", &b"\x2a"[..])] {
+        encoding.extend_from_slice(&(tag.len() as u64).to_be_bytes());
+        encoding.extend_from_slice(tag.as_bytes());
+        encoding.extend_from_slice(&(value.len() as u64).to_be_bytes());
+        encoding.extend_from_slice(value);
+    }
+    let digest = record(&[("name", b"alice"), ("id", b"\x2a")]);
+    assert_eq!(
+        digest,
+        <[u8; 32]>::from(Sha256::digest(&encoding)),
+        "Mismatch on the record encoding."
+    );
+
+    // === Test 2: Reordering fields changes the digest ===
+    assert_ne!(
+        record(&[("id", b"\x2a"), ("name", b"alice")]),
+        digest,
+        "Reordered fields collide."
+    );
+
+    // === Test 3: Moving a byte across a field boundary changes the digest ===
+    assert_ne!(
+        record(&[("a", b"xy"), ("b", b"z")]),
+        record(&[("a", b"x"), ("b", b"yz")]),
+        "Shifted value byte collides."
+    );
+    assert_ne!(
+        record(&[("ab", b"c")]),
+        record(&[("a", b"bc")]),
+        "Shifted tag byte collides."
+    );
+}
+
+/// Tests hashing the little-endian serialization against `sha2` and the big-endian path.
+#[test]
+fn hash_field_le_test() {
+    use ark_ff::UniformRand;
+    use kimchi::o1_utils::tests;
+    use sha2::{Digest, Sha256};
+
+    let mut rng = tests::make_test_rng(None);
+    for x in [Fp::from(1u8), Fp::rand(&mut rng)] {
+        let le_bytes = x.to_bytes();
+        assert_eq!(le_bytes.len(), 32, "Serialization width mismatch.");
+        assert_eq!(
+            digest_to_bytes(hash_field_le(x)),
+            <[u8; 32]>::from(Sha256::digest(&le_bytes)),
+            "Mismatch with standard SHA256 of the little-endian bytes."
+        );
+        assert_ne!(
+            hash_field_le(x),
+            hash_fields(&[x]),
+            "Little- and big-endian hashes must differ."
+        );
+    }
+}
+
+/// Tests that the startup self-check passes on the correct implementation.
+#[test]
+fn self_check_test() {
+    assert!(self_check::<Fp>(), "Self-check failed.");
+}
+
+/// Tests the digest-and-counter hash against `sha2` over the same 36 bytes.
+#[test]
+fn hash_digest_counter_test() {
+    use sha2::{Digest, Sha256};
+
+    let prev = NativeSha256::<Fp>::hash_bytes(b"kdf seed");
+    for counter in [0u32, 1, 0x0102_0304, u32::MAX] {
+        let preimage = [&digest_to_bytes(prev)[..], &counter.to_be_bytes()].concat();
+        assert_eq!(
+            digest_to_bytes(hash_digest_counter(prev, counter)),
+            <[u8; 32]>::from(Sha256::digest(&preimage)),
+            "Mismatch with sha2 on counter {counter}."
+        );
+    }
+    assert_ne!(
+        hash_digest_counter(prev, 7),
+        hash_digest_counter(prev, 8),
+        "Incrementing the counter must change the digest."
+    );
+}
+
+/// Tests that batch verification flags each pair by index.
+#[test]
+fn verify_batch_test() {
+    use sha2::{Digest, Sha256};
+
+    let digest = |data: &[u8]| <[u8; 32]>::from(Sha256::digest(data));
+    let long = [0x42u8; 150];
+    let pairs: Vec<(&[u8], [u8; 32])> = vec![
+        (b"", digest(b"")),
+        (b"abc", digest(b"abd")),
+        (&long, digest(&long)),
+        (b"manifest", [0u8; 32]),
+        (b"abc", digest(b"abc")),
+    ];
+    assert_eq!(
+        verify_batch::<Fp>(&pairs),
+        vec![true, false, true, false, true],
+        "Results must align with the pairs by index."
+    );
+    assert!(
+        verify_batch::<Fp>(&[]).is_empty(),
+        "Empty batch must give no results."
+    );
+}
+
+/// Tests that the tee forwards bytes intact and hashes them, also across short writes.
+#[test]
+fn hashing_writer_test() {
+    /// Inner writer accepting at most 7 bytes per call.
+    struct Trickle(Vec<u8>);
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let n = buf.len().min(7);
+            self.0.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let data: Vec<u8> = (0..200u8).collect();
+
+    let mut tee = HashingWriter::<Fp, _>::new(Vec::new());
+    tee.write_all(&data[..70]).unwrap();
+    tee.write_all(&data[70..]).unwrap();
+    tee.flush().unwrap();
+    let (forwarded, digest) = tee.finish();
+    assert_eq!(forwarded, data, "Forwarded bytes corrupted.");
+    assert_eq!(
+        digest,
+        NativeSha256::<Fp>::hash_bytes(&data),
+        "Mismatch with hashing the data directly."
+    );
+
+    let mut tee = HashingWriter::<Fp, _>::new(Trickle(Vec::new()));
+    io::copy(&mut &data[..], &mut tee).unwrap();
+    let (Trickle(forwarded), digest) = tee.finish();
+    assert_eq!(
+        forwarded, data,
+        "Forwarded bytes corrupted by short writes."
+    );
+    assert_eq!(
+        digest,
+        NativeSha256::<Fp>::hash_bytes(&data),
+        "Mismatch after short writes."
+    );
+}
@@ -0,0 +1,143 @@
+use ark_ff::PrimeField;
+
+use crate::{
+    native_sha256::{NativeSha256, StreamingSha256},
+    sha_helpers::*,
+};
+
+/// SHA256 block size in bytes, the HMAC key width.
+const BLOCK_BYTES: usize = 64;
+
+/// Computes HMAC-SHA256 (RFC 2104) of `message` under `key` with the field engine.
+/// Keys longer than one block are hashed first; shorter keys are zero-padded.
+#[must_use]
+pub fn hmac_sha256<F: PrimeField>(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block_key = [0u8; BLOCK_BYTES];
+    if key.len() > BLOCK_BYTES {
+        block_key[..32].copy_from_slice(&digest_to_bytes(NativeSha256::<F>::hash_bytes(key)));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = StreamingSha256::<F>::new();
+    inner.update(&block_key.map(|byte| byte ^ 0x36));
+    inner.update(message);
+
+    let mut outer = StreamingSha256::<F>::new();
+    outer.update(&block_key.map(|byte| byte ^ 0x5c));
+    outer.update(&digest_to_bytes(inner.finalize()));
+    digest_to_bytes(outer.finalize())
+}
+
+/// HKDF-Extract (RFC 5869): derives a pseudorandom key `HMAC(salt, ikm)`.
+/// An empty `salt` stands for the RFC's default of 32 zero bytes, which HMAC pads identically.
+#[must_use]
+pub fn hkdf_extract<F: PrimeField>(salt: &[u8], ikm: &[u8]) -> [u8; 32] {
+    hmac_sha256::<F>(salt, ikm)
+}
+
+/// HKDF-Expand (RFC 5869): derives `length` bytes of output keying material from `prk`,
+/// bound to the context `info`.
+/// Panics if `length` exceeds the RFC limit of 255 blocks (8160 bytes).
+#[must_use]
+pub fn hkdf_expand<F: PrimeField>(prk: &[u8], info: &[u8], length: usize) -> Vec<u8> {
+    assert!(length <= 255 * 32, "HKDF output is limited to 8160 bytes!");
+
+    let mut okm = Vec::with_capacity(length);
+    let mut block: Vec<u8> = Vec::new();
+    for counter in 1..=length.div_ceil(32) as u8 {
+        block = hmac_sha256::<F>(prk, &[&block[..], info, &[counter]].concat()).to_vec();
+        okm.extend_from_slice(&block);
+    }
+    okm.truncate(length);
+    okm
+}
+
+/// Tests HKDF against the RFC 5869 SHA256 vectors and the `hkdf` crate.
+#[test]
+fn hkdf_test() {
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::Sha256;
+
+    // (ikm, salt, info, length, prk, okm) from RFC 5869, test cases 1-3.
+    let vectors = [
+        (
+            "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+            "000102030405060708090a0b0c",
+            "f0f1f2f3f4f5f6f7f8f9",
+            42,
+            "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf\
+             34007208d5b887185865",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+             202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f\
+             404142434445464748494a4b4c4d4e4f",
+            "606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c7d7e7f\
+             808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f\
+             a0a1a2a3a4a5a6a7a8a9aaabacadaeaf",
+            "b0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecf\
+             d0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeef\
+             f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+            82,
+            "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+            "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c\
+             59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71\
+             cc30c58179ec3e87c14c01d5c1f3434f1d87",
+        ),
+        (
+            "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+            "",
+            "",
+            42,
+            "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d\
+             9d201395faa4b61a96c8",
+        ),
+    ];
+
+    for (i, (ikm, salt, info, length, prk, okm)) in vectors.into_iter().enumerate() {
+        let (ikm, salt, info) = (
+            hex::decode(ikm).unwrap(),
+            hex::decode(salt).unwrap(),
+            hex::decode(info).unwrap(),
+        );
+        let derived_prk = hkdf_extract::<Fp>(&salt, &ikm);
+        assert_eq!(
+            hex::encode(derived_prk),
+            prk,
+            "Mismatch on PRK of case {}.",
+            i + 1
+        );
+        assert_eq!(
+            hex::encode(hkdf_expand::<Fp>(&derived_prk, &info, length)),
+            okm,
+            "Mismatch on OKM of case {}.",
+            i + 1
+        );
+
+        // Same output as the `hkdf` crate, also for lengths around block boundaries.
+        let reference = hkdf::Hkdf::<Sha256>::new(Some(&salt), &ikm);
+        for length in [0, 1, 31, 32, 33, 64, 100] {
+            let mut expected = vec![0u8; length];
+            reference.expand(&info, &mut expected).unwrap();
+            assert_eq!(
+                hkdf_expand::<Fp>(&derived_prk, &info, length),
+                expected,
+                "Mismatch with the hkdf crate on case {} for {length} bytes.",
+                i + 1
+            );
+        }
+    }
+
+    // Keys longer than one block are hashed before use.
+    let long_key = [0xaau8; 131];
+    let mut mac = <hmac::Hmac<Sha256> as hmac::Mac>::new_from_slice(&long_key).unwrap();
+    hmac::Mac::update(&mut mac, b"long key");
+    assert_eq!(
+        hmac_sha256::<Fp>(&long_key, b"long key").to_vec(),
+        hmac::Mac::finalize(mac).into_bytes().to_vec(),
+        "Mismatch with the hmac crate on a long key."
+    );
+}
@@ -1,4 +1,23 @@
+#[cfg(feature = "hw-accel")]
+pub mod accel;
+#[cfg(feature = "async")]
+pub mod async_sha256;
+#[cfg(feature = "bool-backend")]
+pub mod bool_backend;
+pub mod builder;
+pub mod commitment;
+pub mod compression;
 pub mod constants;
+pub mod digest;
 pub mod dynamic_sha256;
+pub mod errors;
+pub mod hashing;
+pub mod kdf;
+pub mod merkle;
 pub mod native_sha256;
+pub mod params;
+pub mod pow;
+pub mod quick;
 pub mod sha_helpers;
+
+pub use hashing::{sha256, FieldElems, HashInput};
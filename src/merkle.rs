@@ -0,0 +1,296 @@
+use std::marker::PhantomData;
+
+use ark_ff::PrimeField;
+
+use crate::{
+    digest::Digest,
+    native_sha256::{NativeSha256, StreamingSha256},
+    sha_helpers::*,
+};
+
+/// Hashes two child digests into their parent: `SHA256(left || right)` over the 64 digest bytes.
+#[must_use]
+pub fn hash_pair<F: PrimeField>(left: Digest<F>, right: Digest<F>) -> Digest<F> {
+    let mut hasher = StreamingSha256::<F>::new();
+    hasher.update(&digest_to_bytes(left));
+    hasher.update(&digest_to_bytes(right));
+    hasher.finalize()
+}
+
+/// Verifies a Merkle inclusion path from `leaf` up to `root`.
+///
+/// Each path element holds the sibling digest and the position bit of the current node at that
+/// level, ordered from the leaves upwards: `false` when the current node is the left child
+/// (`hash_pair(node, sibling)`), `true` when it is the right child (`hash_pair(sibling, node)`).
+/// The bits are therefore the leaf index in little-endian order. The recomputed root is
+/// compared to `root` in constant time.
+#[must_use]
+pub fn verify_merkle_path<F: PrimeField>(
+    leaf: [u8; 32],
+    path: &[(Digest<F>, bool)],
+    root: Digest<F>,
+) -> bool {
+    let computed = path
+        .iter()
+        .fold(bytes_to_digest(&leaf), |node, &(sibling, is_right)| {
+            if is_right {
+                hash_pair(sibling, node)
+            } else {
+                hash_pair(node, sibling)
+            }
+        });
+
+    constant_time_eq(&digest_to_bytes(computed), &digest_to_bytes(root))
+}
+
+/// Domain-separation prefixes for [`MerkleTree::root_prefixed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MerklePrefixes {
+    /// Byte prepended to every leaf before hashing.
+    pub leaf_prefix: u8,
+    /// Byte prepended to every pair of child digests before hashing.
+    pub node_prefix: u8,
+}
+
+impl MerklePrefixes {
+    /// The RFC 6962 (Certificate Transparency) prefixes: `0x00` for leaves, `0x01` for nodes.
+    pub const RFC6962: Self = Self {
+        leaf_prefix: 0x00,
+        node_prefix: 0x01,
+    };
+}
+
+/// Binary SHA256 Merkle tree over 32-byte leaves.
+/// Leaves are used as-is for the bottom level; a level with an odd number of nodes pairs its
+/// last node with itself.
+pub struct MerkleTree<F: PrimeField> {
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField> MerkleTree<F> {
+    /// Computes the Merkle root of `leaves`.
+    #[must_use]
+    pub fn root(leaves: &[[u8; 32]]) -> Digest<F> {
+        let mut level = Self::leaf_level(leaves);
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| hash_pair(pair[0], *pair.last().unwrap()))
+                .collect();
+        }
+        level[0]
+    }
+
+    /// Computes the Merkle root of `leaves`, hashing the sibling pairs of each level in parallel.
+    /// Produces the same root as [`MerkleTree::root`].
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    #[must_use]
+    pub fn root_parallel(leaves: &[[u8; 32]]) -> Digest<F> {
+        use rayon::prelude::*;
+
+        let mut level = Self::leaf_level(leaves);
+        while level.len() > 1 {
+            level = level
+                .par_chunks(2)
+                .map(|pair| hash_pair(pair[0], *pair.last().unwrap()))
+                .collect();
+        }
+        level[0]
+    }
+
+    /// Computes the Merkle root of `leaves`. wasm32 has no threads, so this falls back to
+    /// [`MerkleTree::root`].
+    #[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+    #[must_use]
+    pub fn root_parallel(leaves: &[[u8; 32]]) -> Digest<F> {
+        Self::root(leaves)
+    }
+
+    /// Computes the root of a domain-separated tree over leaves of any length, RFC 6962 style.
+    ///
+    /// Every leaf is hashed as `SHA256(leaf_prefix || leaf)` and every node as
+    /// `SHA256(node_prefix || left || right)`, so a leaf can never collide with an internal node
+    /// (a second-preimage attack across levels). Unlike [`MerkleTree::root`], unbalanced levels
+    /// are not filled by duplication: `n` leaves split into the largest power of two below `n` on
+    /// the left and the rest on the right. With [`MerklePrefixes::RFC6962`] this is the
+    /// Certificate Transparency tree hash.
+    #[must_use]
+    pub fn root_prefixed(leaves: &[&[u8]], prefixes: MerklePrefixes) -> Digest<F> {
+        assert!(!leaves.is_empty(), "Merkle tree needs at least one leaf!");
+        if let [leaf] = leaves {
+            return NativeSha256::hash_concat(&[&[prefixes.leaf_prefix], leaf]);
+        }
+
+        let split = 1 << (leaves.len() - 1).ilog2();
+        let left = Self::root_prefixed(&leaves[..split], prefixes);
+        let right = Self::root_prefixed(&leaves[split..], prefixes);
+        NativeSha256::hash_concat(&[
+            &[prefixes.node_prefix],
+            &digest_to_bytes(left),
+            &digest_to_bytes(right),
+        ])
+    }
+
+    /// Converts the leaves into the bottom level of the tree.
+    fn leaf_level(leaves: &[[u8; 32]]) -> Vec<Digest<F>> {
+        assert!(!leaves.is_empty(), "Merkle tree needs at least one leaf!");
+        leaves.iter().map(bytes_to_digest).collect()
+    }
+}
+
+/// Tests the Merkle root against a manual computation with `sha2`.
+#[test]
+fn merkle_root_test() {
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    let leaves: Vec<[u8; 32]> = (0u8..5).map(|i| Sha256::digest([i]).into()).collect();
+    let parent = |l: &[u8], r: &[u8]| -> [u8; 32] { Sha256::digest([l, r].concat()).into() };
+
+    // Level 1: (0,1), (2,3), (4,4).
+    let n01 = parent(&leaves[0], &leaves[1]);
+    let n23 = parent(&leaves[2], &leaves[3]);
+    let n44 = parent(&leaves[4], &leaves[4]);
+    // Level 2: (01,23), (44,44).
+    let n0123 = parent(&n01, &n23);
+    let n4444 = parent(&n44, &n44);
+    let expected = parent(&n0123, &n4444);
+
+    let root = MerkleTree::<Fp>::root(&leaves);
+    assert_eq!(digest_to_bytes(root), expected, "Mismatch on Merkle root.");
+
+    let single = MerkleTree::<Fp>::root(&leaves[..1]);
+    assert_eq!(
+        digest_to_bytes(single),
+        leaves[0],
+        "Single leaf must be the root."
+    );
+}
+
+/// Tests that a valid inclusion path is accepted and tampered ones are rejected.
+#[test]
+fn verify_merkle_path_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let leaves: Vec<[u8; 32]> = (0u8..4).map(|i| [i; 32]).collect();
+    let digests: Vec<[[Fp; 32]; 8]> = leaves.iter().map(bytes_to_digest).collect();
+    let root = MerkleTree::<Fp>::root(&leaves);
+
+    // Leaf 2 is the left child of (2, 3), which is the right child of the root.
+    let n01 = hash_pair(digests[0], digests[1]);
+    let path = [(digests[3], false), (n01, true)];
+    assert!(
+        verify_merkle_path(leaves[2], &path, root),
+        "Valid path rejected."
+    );
+
+    let mut tampered = path;
+    tampered[0].0[0][0] = Fp::from(1u8) - tampered[0].0[0][0];
+    assert!(
+        !verify_merkle_path(leaves[2], &tampered, root),
+        "Tampered sibling accepted."
+    );
+
+    let mut flipped = path;
+    flipped[1].1 = false;
+    assert!(
+        !verify_merkle_path(leaves[2], &flipped, root),
+        "Flipped position bit accepted."
+    );
+}
+
+/// Tests domain separation and the RFC 6962 reference roots.
+#[test]
+fn merkle_root_prefixed_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    // === Test 1: A leaf never hashes like a node over the same bytes ===
+    let (left, right) = ([0x11u8; 32], [0x22u8; 32]);
+    let concatenated = [left, right].concat();
+    let node = MerkleTree::<Fp>::root_prefixed(&[&left, &right], MerklePrefixes::RFC6962);
+    let leaf = MerkleTree::<Fp>::root_prefixed(&[&concatenated], MerklePrefixes::RFC6962);
+    assert_ne!(
+        digest_to_bytes(node),
+        digest_to_bytes(leaf),
+        "Leaf and node digests collide."
+    );
+
+    // Without separation, a forged leaf holding the two child digests matches the node.
+    let same = MerklePrefixes {
+        leaf_prefix: 0x01,
+        node_prefix: 0x01,
+    };
+    let forged = [
+        digest_to_bytes(MerkleTree::<Fp>::root_prefixed(&[&left], same)),
+        digest_to_bytes(MerkleTree::<Fp>::root_prefixed(&[&right], same)),
+    ]
+    .concat();
+    assert_eq!(
+        MerkleTree::<Fp>::root_prefixed(&[&left, &right], same),
+        MerkleTree::<Fp>::root_prefixed(&[&forged], same),
+        "Equal prefixes must allow the cross-level collision."
+    );
+
+    // === Test 2: RFC 6962 reference roots from the Certificate Transparency test data ===
+    let leaves: [&[u8]; 8] = [
+        b"",
+        b"\x00",
+        b"\x10",
+        b"\x20\x21",
+        b"\x30\x31",
+        b"\x40\x41\x42\x43",
+        b"\x50\x51\x52\x53\x54\x55\x56\x57",
+        b"\x60\x61\x62\x63\x64\x65\x66\x67\x68\x69\x6a\x6b\x6c\x6d\x6e\x6f",
+    ];
+    for (n, expected) in [
+        (
+            1,
+            "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+        ),
+        (
+            2,
+            "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+        ),
+        (
+            3,
+            "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77",
+        ),
+        (
+            5,
+            "4e3bbb1f7b478dcfe71fb631631519a3bca12c9aefca1612bfce4c13a86264d4",
+        ),
+        (
+            7,
+            "ddb89be403809e325750d3d263cd78929c2942b7942a34b77e122c9594a74c8c",
+        ),
+        (
+            8,
+            "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328",
+        ),
+    ] {
+        assert_eq!(
+            digest_to_hex(MerkleTree::<Fp>::root_prefixed(
+                &leaves[..n],
+                MerklePrefixes::RFC6962
+            )),
+            expected,
+            "Mismatch on the RFC 6962 root of {n} leaves."
+        );
+    }
+}
+
+/// Tests that the parallel Merkle root equals the serial one.
+#[cfg(feature = "parallel")]
+#[test]
+fn merkle_root_parallel_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let leaves: Vec<[u8; 32]> = (0u8..13).map(|i| [i; 32]).collect();
+    for n in [1, 2, 3, 8, 13] {
+        assert_eq!(
+            MerkleTree::<Fp>::root_parallel(&leaves[..n]),
+            MerkleTree::<Fp>::root(&leaves[..n]),
+            "Mismatch between parallel and serial roots for {n} leaves."
+        );
+    }
+}
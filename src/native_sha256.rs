@@ -1,205 +1,1543 @@
-#![allow(non_snake_case)]
-
-use std::marker::PhantomData;
-
-use ark_ff::{PrimeField, UniformRand};
-
-use kimchi::{
-    mina_curves::pasta::Fp,
-    o1_utils::{tests, FieldHelpers},
-};
-use sha2::{Digest, Sha256};
-
-use crate::{constants::*, sha_helpers::*};
-
-/// Native SHA256 implementation using field elements.
-/// This is used to simulate and test SHA256 logic before building a circuit-compatible version.
-pub struct NativeSha256<F: PrimeField> {
-    padded_preimage: Vec<u8>,
-    _marker: PhantomData<F>,
-}
-
-impl<F: PrimeField> NativeSha256<F> {
-    /// Constructor.
-    pub fn new(padded_preimage: Vec<u8>) -> Self {
-        Self {
-            padded_preimage,
-            _marker: PhantomData,
-        }
-    }
-
-    /// Processes a single 512-bit message chunk, applying SHA256 compression.
-    /// Updates internal state by applying 64 rounds of the SHA256 schedule and mixing.
-    fn process_chunk(&mut self, bits: &[u8], state: &mut [[F; 32]; 8], K: [[F; 32]; 64]) {
-        assert_eq!(bits.len(), 512, "Chunk must be 512 bits");
-
-        // Message schedule W.
-        let field_values = bits_to_field::<F, 512>(&bits);
-        let mut W = [[F::zero(); 32]; 64];
-        for (i, chunk) in field_values.chunks_exact(32).enumerate() {
-            W[i].copy_from_slice(chunk);
-        }
-
-        for i in 16..64 {
-            let s0 = xor(
-                xor(rotate_right(7, W[i - 15]), rotate_right(18, W[i - 15])),
-                right_shift(3, W[i - 15]),
-            );
-            let s1 = xor(
-                xor(rotate_right(17, W[i - 2]), rotate_right(19, W[i - 2])),
-                right_shift(10, W[i - 2]),
-            );
-            W[i] = wrapping_add(wrapping_add(s1, W[i - 7]), wrapping_add(s0, W[i - 16]));
-        }
-
-        // Compression loop.
-        let (mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h) = (
-            state[0], state[1], state[2], state[3], state[4], state[5], state[6], state[7],
-        );
-
-        for i in 0..64 {
-            let S1 = xor(
-                xor(rotate_right(6, e), rotate_right(11, e)),
-                rotate_right(25, e),
-            );
-            let Ch = xor(and(e, f), and(not(e), g));
-            let T1 = wrapping_add(
-                wrapping_add(wrapping_add(wrapping_add(h, S1), Ch), K[i]),
-                W[i],
-            );
-
-            let S0 = xor(
-                xor(rotate_right(2, a), rotate_right(13, a)),
-                rotate_right(22, a),
-            );
-            let Maj = xor(xor(and(a, b), and(a, c)), and(b, c));
-            let T2 = wrapping_add(S0, Maj);
-
-            h = g;
-            g = f;
-            f = e;
-            e = wrapping_add(d, T1);
-            d = c;
-            c = b;
-            b = a;
-            a = wrapping_add(T1, T2);
-        }
-
-        // Final state update.
-        state[0] = wrapping_add(a, state[0]);
-        state[1] = wrapping_add(b, state[1]);
-        state[2] = wrapping_add(c, state[2]);
-        state[3] = wrapping_add(d, state[3]);
-        state[4] = wrapping_add(e, state[4]);
-        state[5] = wrapping_add(f, state[5]);
-        state[6] = wrapping_add(g, state[6]);
-        state[7] = wrapping_add(h, state[7]);
-    }
-
-    /// Computes the SHA256 hash over the (already padded) input bitstream.
-    pub fn hash(mut self) -> [[F; 32]; 8] {
-        assert!(
-            &self.padded_preimage.len() % 512 == 0,
-            "Input must be padded to 512-bit blocks."
-        );
-
-        let mut state = initial_state();
-        let K = round_constants();
-
-        let chunks: Vec<Vec<u8>> = self
-            .padded_preimage
-            .chunks(512)
-            .map(|chunk| chunk.to_vec())
-            .collect();
-
-        for chunk in chunks {
-            self.process_chunk(&chunk, &mut state, K);
-        }
-
-        // Output digest as [[F; 32]; 8] bit representation.
-        state
-    }
-}
-
-/// Tests native SHA256 logic against Rust's standard `sha2` implementation.
-#[test]
-fn native_sha256_test() {
-    // === Test 1: SHA256 of a zero byte ===
-    let zero_bits = from_hex("00");
-    let (padded, _) = sha256_pad(zero_bits, 512);
-    let zero_hash = NativeSha256::<Fp>::new(padded).hash();
-    // Output digest as hex string.
-    let zero_hash_hex = digest_to_hex(zero_hash);
-
-    // Standart Sha256.
-    let zero_std = Sha256::digest(&[0u8]);
-    let zero_std_hex = hex::encode(zero_std);
-
-    assert_eq!(zero_hash_hex, zero_std_hex, "Mismatch on 0x00.");
-
-    // === Test 2: SHA256 of a random field elements ===
-    let mut rng = tests::make_test_rng(None);
-    let first_random = Fp::rand(&mut rng);
-    let second_random = Fp::rand(&mut rng);
-    let first_random_hex = first_random.to_hex();
-    let second_random_hex = second_random.to_hex();
-    let concatenated = format!("{}{}", first_random_hex, second_random_hex);
-
-    let bits = from_hex(&concatenated);
-    let (padded, digest_index) = sha256_pad(bits, 1024);
-    let hash_index = 960;
-    let native_hash = NativeSha256::<Fp>::new(padded).hash();
-    // Output digest as hex string.
-    let native_hash_hex = digest_to_hex(native_hash);
-
-    // Standart Sha256.
-    let bytes = hex::decode(&concatenated).unwrap();
-    let std_hash = Sha256::digest(&bytes);
-    let std_hash_hex = hex::encode(std_hash);
-
-    assert_eq!(
-        hash_index, digest_index,
-        "Mismatch between hash index and expected hash index."
-    );
-
-    assert_eq!(
-        native_hash_hex, std_hash_hex,
-        "Mismatch between native and standard SHA256."
-    );
-
-    // === Test 3: SHA256 of a random field elements ===
-    // Given random 4 Fp elements.
-    let rand_numbers = [
-        Fp::rand(&mut rng),
-        Fp::rand(&mut rng),
-        Fp::rand(&mut rng),
-        Fp::rand(&mut rng),
-    ];
-    let rand_numbers_hex: Vec<String> = rand_numbers.iter().map(|num| num.to_hex()).collect();
-    let merged_hex: String = rand_numbers_hex
-        .iter()
-        .flat_map(|num| num.chars())
-        .collect();
-
-    let bits = from_hex(&merged_hex);
-    let (padded, digest_index) = sha256_pad(bits, 1536);
-    let hash_index = 1472;
-    let native_hash = NativeSha256::<Fp>::new(padded).hash();
-    // Output digest as hex string.
-    let native_hash_hex = digest_to_hex(native_hash);
-
-    // Standart Sha256.
-    let bytes = hex::decode(&merged_hex).unwrap();
-    let std_hash = Sha256::digest(&bytes);
-    let std_hash_hex = hex::encode(std_hash);
-
-    assert_eq!(
-        hash_index, digest_index,
-        "Mismatch between hash index and expected hash index."
-    );
-
-    assert_eq!(
-        native_hash_hex, std_hash_hex,
-        "Mismatch between native and standard SHA256."
-    );
-}
+#![allow(non_snake_case)]
+
+use std::{io::IoSlice, marker::PhantomData};
+
+use ark_ff::{PrimeField, UniformRand};
+
+use kimchi::{
+    mina_curves::pasta::Fp,
+    o1_utils::{tests, FieldHelpers},
+};
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    compression::{
+        compress_with_schedule, message_schedule, BlockTraces, Compressor, Sha256Compressor,
+    },
+    constants::*,
+    digest::Digest,
+    errors::Sha256Error,
+    sha_helpers::*,
+};
+
+/// Native SHA256 implementation using field elements.
+/// This is used to simulate and test SHA256 logic before building a circuit-compatible version.
+///
+/// It hashes an already padded preimage in one shot via [`NativeSha256::hash`]; raw bytes are
+/// absorbed incrementally by [`StreamingSha256`] instead.
+///
+/// Blocks are compressed with the [`Compressor`] `C`, the standard SHA256 compression unless
+/// specified. Trace and working-variable inspection run through `C` as well.
+#[derive(Clone)]
+pub struct NativeSha256<F: PrimeField, C: Compressor<F> = Sha256Compressor> {
+    padded_preimage: Vec<u8>,
+    _marker: PhantomData<(F, C)>,
+}
+
+/// Streaming SHA256 using field elements: absorbs raw bytes incrementally with
+/// [`StreamingSha256::update`] and pads them by their true length on
+/// [`StreamingSha256::finalize`] or [`StreamingSha256::finalize_reset`].
+///
+/// Blocks are compressed with the [`Compressor`] `C`, the standard SHA256 compression unless
+/// specified.
+#[derive(Clone)]
+pub struct StreamingSha256<F: PrimeField, C: Compressor<F> = Sha256Compressor> {
+    state: [[F; 32]; 8],
+    buffer: Vec<u8>,
+    length: u64,
+    compressor: PhantomData<C>,
+}
+
+/// Size statistics of a hash over a padded preimage, from [`NativeSha256::hash_with_stats`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashStats {
+    /// Number of 512-bit blocks compressed.
+    pub blocks: usize,
+    /// Length of the message, as recorded in its length field.
+    pub input_bits: usize,
+    /// Bits added by padding: the marker, zero fill, length field and any extra capacity blocks.
+    pub padding_bits: usize,
+}
+
+/// When [`NativeSha256::hash_with_strategy`] expands the message schedules.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScheduleStrategy {
+    /// Expand each block's schedule right before compressing it, as [`NativeSha256::hash`]
+    /// does. Only one schedule is alive at a time.
+    #[default]
+    Lazy,
+    /// Expand the schedules of all blocks up front, then run the compressions over them. The
+    /// schedules depend only on the message, so with the `parallel` feature they are expanded
+    /// concurrently; the compressions stay sequential. Holds every schedule at once, 2048 field
+    /// elements per block.
+    Eager,
+}
+
+/// Chaining state after a block-aligned message prefix, from [`NativeSha256::precompute_prefix`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrefixState<F: PrimeField> {
+    state: [[F; 32]; 8],
+    length: u64,
+}
+
+impl<F: PrimeField> NativeSha256<F> {
+    /// Constructor.
+    pub fn new(padded_preimage: Vec<u8>) -> Self {
+        Self::with_compressor(padded_preimage)
+    }
+
+    /// Returns the padded preimage packed into bytes and hex-encoded, for comparing the layout
+    /// with a circuit's. Panics if the preimage is not a whole number of bytes.
+    #[must_use]
+    pub fn padded_preimage_hex(&self) -> String {
+        hex::encode(bits_to_bytes(&self.padded_preimage))
+    }
+
+    /// Hashes a byte message in one call, padding it according to its true length.
+    #[must_use]
+    pub fn hash_bytes(data: &[u8]) -> Digest<F> {
+        let mut hasher = StreamingSha256::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Hashes the concatenation of scattered buffers, iovec style: each slice is streamed through
+    /// [`StreamingSha256::update`] in order, so the fragments are never coalesced into one buffer.
+    #[must_use]
+    pub fn hash_vectored(bufs: &[IoSlice<'_>]) -> Digest<F> {
+        let mut hasher = StreamingSha256::new();
+        for buf in bufs {
+            hasher.update(buf);
+        }
+        hasher.finalize()
+    }
+
+    /// Hashes a byte message expanded to bits in the given [`BitOrder`]. With [`BitOrder::Msb0`]
+    /// this is [`NativeSha256::hash_bytes`]; [`BitOrder::Lsb0`] is non-standard and equals the
+    /// SHA256 of the bit-reversed bytes, for interop with legacy systems only.
+    #[must_use]
+    pub fn hash_bytes_ordered(data: &[u8], order: BitOrder) -> Digest<F> {
+        Self::hash_bits(bytes_to_bits_ordered(data, order))
+    }
+
+    /// Hashes a `u32` encoded as its 4 big-endian bytes.
+    #[must_use]
+    pub fn hash_u32_be(x: u32) -> Digest<F> {
+        Self::hash_bits(to_bits_be::<_, 32>(x).to_vec())
+    }
+
+    /// Hashes a `u64` encoded as its 8 big-endian bytes.
+    #[must_use]
+    pub fn hash_u64_be(x: u64) -> Digest<F> {
+        Self::hash_bits(to_bits_be::<_, 64>(x).to_vec())
+    }
+
+    /// Hashes an `i64` encoded as its 8 big-endian two's complement bytes, i.e. the same
+    /// preimage as `hash_u64_be(x as u64)`.
+    #[must_use]
+    pub fn hash_i64_be(x: i64) -> Digest<F> {
+        Self::hash_u64_be(x as u64)
+    }
+
+    /// Pads an unpadded bit message to the minimal number of blocks and hashes it.
+    fn hash_bits(bits: Vec<u8>) -> Digest<F> {
+        let max_bits = min_padded_bits(bits.len());
+        let (padded, _) = sha256_pad(bits, max_bits).expect("Minimal padding always fits.");
+        Self::new(padded).hash()
+    }
+
+    /// Hashes an already padded preimage given as packed bytes, e.g. from [`sha256_pad_bytes`].
+    /// This is the byte form of the bit vector taken by [`NativeSha256::new`]: only one block at
+    /// a time is expanded to bits, so memory stays at the input size instead of growing 8x.
+    #[must_use]
+    pub fn hash_packed(padded_bytes: &[u8]) -> Digest<F> {
+        assert!(
+            padded_bytes.len().is_multiple_of(64),
+            "Input must be padded to 512-bit blocks."
+        );
+
+        let mut state = initial_state();
+        let K = round_constants();
+
+        for (i, block) in padded_bytes.chunks_exact(64).enumerate() {
+            let _span = block_span(i);
+            Self::process_chunk(&bytes_to_bits(block), &mut state, K);
+        }
+        trace_digest(state);
+
+        state
+    }
+
+    /// Hashes the concatenation of `parts`, streaming each part without building the combined
+    /// message.
+    #[must_use]
+    pub fn hash_concat(parts: &[&[u8]]) -> Digest<F> {
+        let mut hasher = StreamingSha256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize()
+    }
+
+    /// Computes the SHA256 and SHA224 digests of `data` in one pass. The padding (identical for
+    /// both) and each block's message schedule are computed once and fed to two compressions,
+    /// one from each initial state. The SHA224 digest is the first 7 words of its final state.
+    #[must_use]
+    pub fn hash_both_variants(data: &[u8]) -> (Digest<F>, [[F; 32]; 7]) {
+        let bits = bytes_to_bits(data);
+        let max_bits = min_padded_bits(bits.len());
+        let (padded, _) = sha256_pad(bits, max_bits).expect("Minimal padding always fits.");
+
+        let K = round_constants();
+        let mut sha256 = initial_state();
+        let mut sha224 = initial_state_224();
+        for chunk in padded.chunks(512) {
+            let W = message_schedule(chunk);
+            sha256 = compress_with_schedule(sha256, &W, K);
+            sha224 = compress_with_schedule(sha224, &W, K);
+        }
+
+        (sha256, std::array::from_fn(|i| sha224[i]))
+    }
+
+    /// Computes the hash like [`NativeSha256::hash`], expanding the message schedules as
+    /// `strategy` says. Both strategies give the same digest; they differ only in speed and
+    /// memory, which `benches/schedule_strategy.rs` compares.
+    #[must_use]
+    pub fn hash_with_strategy(self, strategy: ScheduleStrategy) -> Digest<F> {
+        match strategy {
+            ScheduleStrategy::Lazy => self.hash(),
+            ScheduleStrategy::Eager => {
+                assert_padded_bits(&self.padded_preimage);
+
+                #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+                let schedules: Vec<[[F; 32]; 64]> = {
+                    use rayon::prelude::*;
+                    self.padded_preimage
+                        .par_chunks(512)
+                        .map(message_schedule)
+                        .collect()
+                };
+                #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+                let schedules: Vec<[[F; 32]; 64]> = self
+                    .padded_preimage
+                    .chunks(512)
+                    .map(message_schedule)
+                    .collect();
+
+                let K = round_constants();
+                let state = schedules.iter().fold(initial_state(), |state, W| {
+                    compress_with_schedule(state, W, K)
+                });
+                trace_digest(state);
+                state
+            }
+        }
+    }
+
+    /// Hashes a byte message like [`NativeSha256::hash_bytes`], but refuses inputs longer than
+    /// `max_bytes` before doing any work. This is an input-size policy, unrelated to `max_bits`.
+    #[must_use = "a rejected input is only reported through the returned `Result`"]
+    pub fn hash_bounded(data: &[u8], max_bytes: usize) -> Result<Digest<F>, Sha256Error> {
+        if data.len() > max_bytes {
+            return Err(Sha256Error::InputTooLong {
+                len: data.len(),
+                max_bytes,
+            });
+        }
+        Ok(Self::hash_bytes(data))
+    }
+
+    /// Hashes a byte message and keeps only the leading 128 bits of the digest.
+    /// This truncated hash offers at most 64-bit collision resistance and 128-bit preimage
+    /// resistance, and only matches protocols that truncate SHA256 the same way.
+    #[must_use]
+    pub fn hash_128(data: &[u8]) -> [u8; 16] {
+        truncate_digest(Self::hash_bytes(data))
+    }
+
+    /// Hashes a `bitvec` bit slice, taking its bits in slice order (as defined by its `BitOrder`)
+    /// and padding them to the minimal number of blocks.
+    #[cfg(feature = "bitvec")]
+    #[must_use]
+    pub fn hash_bitslice<T, O>(bits: &bitvec::slice::BitSlice<T, O>) -> Digest<F>
+    where
+        T: bitvec::store::BitStore,
+        O: bitvec::order::BitOrder,
+    {
+        Self::hash_bits(bits.iter().map(|bit| *bit as u8).collect())
+    }
+
+    /// Compresses a shared message prefix once so that [`NativeSha256::hash_with_prefix`] can
+    /// reuse it for many suffixes. The prefix must be a whole number of 64-byte blocks, since a
+    /// partial block cannot be compressed before the rest of the message is known.
+    pub fn precompute_prefix(prefix: &[u8]) -> Result<PrefixState<F>, Sha256Error> {
+        if !prefix.len().is_multiple_of(64) {
+            return Err(Sha256Error::PrefixNotBlockAligned { len: prefix.len() });
+        }
+
+        let mut hasher = StreamingSha256::new();
+        hasher.update(prefix);
+        Ok(PrefixState {
+            state: hasher.state,
+            length: hasher.length,
+        })
+    }
+
+    /// Hashes `prefix || suffix` from a precomputed prefix state, compressing only the suffix.
+    #[must_use]
+    pub fn hash_with_prefix(prefix_state: &PrefixState<F>, suffix: &[u8]) -> Digest<F> {
+        let mut hasher = StreamingSha256 {
+            state: prefix_state.state,
+            length: prefix_state.length,
+            ..StreamingSha256::new()
+        };
+        hasher.update(suffix);
+        hasher.finalize()
+    }
+}
+
+impl<F: PrimeField, C: Compressor<F>> NativeSha256<F, C> {
+    /// Constructor for a hasher that compresses blocks with `C` instead of the standard
+    /// compression, e.g. to check that differential tests catch a wrong implementation.
+    pub fn with_compressor(padded_preimage: Vec<u8>) -> Self {
+        Self {
+            padded_preimage,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Processes a single 512-bit message chunk, applying SHA256 compression.
+    /// Updates internal state by applying 64 rounds of the SHA256 schedule and mixing.
+    fn process_chunk(bits: &[u8], state: &mut [[F; 32]; 8], K: [[F; 32]; 64]) {
+        *state = C::compress(*state, bits, K);
+    }
+
+    /// Computes the SHA256 hash over the (already padded) input bitstream.
+    ///
+    /// **Every block is compressed**, including all-zero blocks that [`sha256_pad`] appends when
+    /// `max_bits` exceeds the minimal padded length. Those are real blocks, so the result is the
+    /// standard SHA256 digest only when the preimage was padded to exactly
+    /// [`min_padded_bits`] bits. [`DynamicSha256::hash_message_blocks`] uses the digest index
+    /// to skip capacity blocks instead.
+    ///
+    /// [`DynamicSha256::hash_message_blocks`]: crate::dynamic_sha256::DynamicSha256::hash_message_blocks
+    #[must_use]
+    pub fn hash(self) -> Digest<F> {
+        assert_padded_bits(&self.padded_preimage);
+
+        let block_count = self.padded_preimage.len() / 512;
+        let state = self.hash_prefix(block_count);
+        trace_digest(state);
+
+        // Output digest as [[F; 32]; 8] bit representation.
+        state
+    }
+
+    /// Computes the hash like [`NativeSha256::hash`] and compares its 32 bytes with `expected`
+    /// through [`constant_time_eq`], without a hex round trip.
+    #[must_use]
+    pub fn verify_bytes(self, expected: &[u8; 32]) -> bool {
+        constant_time_eq(&digest_to_bytes(self.hash()), expected)
+    }
+
+    /// Computes the hash like [`NativeSha256::hash`] and reports how padding inflated the input.
+    /// Panics if the preimage is not well-formed [`sha256_pad`] output (see [`validate_padding`]).
+    #[must_use]
+    pub fn hash_with_stats(self) -> (Digest<F>, HashStats) {
+        let input_bits =
+            padded_message_bits(&self.padded_preimage).unwrap_or_else(|err| panic!("{}", err));
+        let stats = HashStats {
+            blocks: self.padded_preimage.len() / 512,
+            input_bits,
+            padding_bits: self.padded_preimage.len() - input_bits,
+        };
+        (self.hash(), stats)
+    }
+
+    /// Computes the hash like [`NativeSha256::hash`] and also returns the working variables
+    /// `a..h` of the final block after its 64 rounds but before the feed-forward addition, i.e.
+    /// the raw block cipher output of [`Compressor::compress_raw`]. Adding them word-wise to the
+    /// chaining state before the final block gives the digest.
+    #[must_use]
+    pub fn hash_with_working_vars(self) -> (Digest<F>, [[F; 32]; 8]) {
+        assert_padded_bits(&self.padded_preimage);
+        let block_count = self.padded_preimage.len() / 512;
+        assert!(block_count > 0, "Input must hold at least one block!");
+
+        let last = self.padded_preimage[(block_count - 1) * 512..].to_vec();
+        let state = self.hash_prefix(block_count - 1);
+        let working = C::compress_raw(state, &last, round_constants());
+        let digest = std::array::from_fn(|i| wrapping_add(working[i], state[i]));
+        trace_digest(digest);
+
+        (digest, working)
+    }
+
+    /// Iterates over the chaining state after each block of the padded input, compressing one
+    /// block per step. The last item is the digest.
+    pub fn block_states(self) -> impl Iterator<Item = Digest<F>> {
+        assert_padded_bits(&self.padded_preimage);
+
+        let K = round_constants();
+        let mut state = initial_state();
+        let blocks = self.padded_preimage.len() / 512;
+        (0..blocks).map(move |i| {
+            let _span = block_span(i);
+            Self::process_chunk(&self.padded_preimage[i * 512..(i + 1) * 512], &mut state, K);
+            state
+        })
+    }
+
+    /// Returns the hex of the chaining state after each block, one line per block, for diffing
+    /// against the intermediate states of another SHA256 implementation.
+    #[must_use]
+    pub fn block_digests_hex(self) -> Vec<String> {
+        self.block_states().map(digest_to_hex).collect()
+    }
+
+    /// Returns the chaining state after compressing exactly the first `block_count` blocks of the
+    /// padded input, starting from the standard initial state.
+    ///
+    /// Each block is compressed exactly as in [`NativeSha256::hash`], including the feed-forward
+    /// addition; the loop simply stops early. With `block_count` equal to the number of padded
+    /// blocks this is the digest. For a smaller count it is *not* the SHA256 digest of the
+    /// message prefix, because the padding and length belong to the final block only; it is the
+    /// midstate a protocol can commit to before the rest of the message is known.
+    /// Panics if `block_count` exceeds the number of padded blocks.
+    #[must_use]
+    pub fn hash_prefix(self, block_count: usize) -> Digest<F> {
+        assert!(
+            block_count <= self.padded_preimage.len() / 512,
+            "Block count exceeds the number of padded blocks!"
+        );
+
+        let mut state = initial_state();
+        let K = round_constants();
+
+        for (i, chunk) in self
+            .padded_preimage
+            .chunks(512)
+            .take(block_count)
+            .enumerate()
+        {
+            let _span = block_span(i);
+            Self::process_chunk(chunk, &mut state, K);
+        }
+
+        state
+    }
+
+    /// Exports the per-round witness of the whole hash as a flat table for circuit generation.
+    ///
+    /// Returns one row per round (64 per block with the standard compressor), each laid out as
+    /// `[a, b, c, d, e, f, g, h, T1, T2, W_i]` with every column a 32-bit big-endian word, so a
+    /// row holds 352 field elements. The working variables are taken at the start of the round.
+    /// This walks the same compression as [`NativeSha256::hash`] but records every round, so use
+    /// `hash` when only the digest is needed.
+    #[must_use]
+    pub fn export_witness(self) -> Vec<Vec<F>> {
+        assert_padded_bits(&self.padded_preimage);
+
+        let mut state = initial_state();
+        let K = round_constants();
+        let mut rows = Vec::with_capacity(self.padded_preimage.len() / 512 * 64);
+
+        for chunk in self.padded_preimage.chunks(512) {
+            let trace = C::compress_traced(state, chunk, K);
+            rows.extend(trace.rounds.iter().map(|round| round.to_row()));
+            state = trace.output;
+        }
+
+        rows
+    }
+
+    /// Computes the hash like [`NativeSha256::hash`] while recording the [`BlockTrace`] of only
+    /// the listed block indices, so debugging one block of a long message keeps memory bounded.
+    /// Traces are returned in block order; indices past the last block are ignored.
+    #[must_use]
+    pub fn hash_with_trace_filter(self, blocks: &[usize]) -> (Digest<F>, BlockTraces<F>) {
+        assert_padded_bits(&self.padded_preimage);
+
+        let mut state = initial_state();
+        let K = round_constants();
+        let mut traces = Vec::new();
+
+        for (i, chunk) in self.padded_preimage.chunks(512).enumerate() {
+            let _span = block_span(i);
+            if blocks.contains(&i) {
+                let trace = C::compress_traced(state, chunk, K);
+                state = trace.output;
+                traces.push((i, trace));
+            } else {
+                Self::process_chunk(chunk, &mut state, K);
+            }
+        }
+        trace_digest(state);
+
+        (state, traces)
+    }
+}
+
+impl<F: PrimeField> Default for StreamingSha256<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> StreamingSha256<F> {
+    /// Creates a streaming hasher with nothing absorbed.
+    pub fn new() -> Self {
+        Self::with_compressor()
+    }
+}
+
+impl<F: PrimeField, C: Compressor<F>> StreamingSha256<F, C> {
+    /// Creates a streaming hasher that compresses blocks with `C` instead of the standard
+    /// compression.
+    pub fn with_compressor() -> Self {
+        Self {
+            state: initial_state(),
+            buffer: Vec::with_capacity(64),
+            length: 0,
+            compressor: PhantomData,
+        }
+    }
+
+    /// Absorbs message bytes, compressing every complete 512-bit block into the running state.
+    /// Bytes that do not fill a block are buffered until the next `update` or finalization.
+    ///
+    /// Panics once the total message reaches 2^64 bits, which SHA256's 64-bit length field
+    /// cannot record; [`sha256_pad`] rejects the same messages with
+    /// [`MessageTooLong`](crate::errors::PadError::MessageTooLong).
+    pub fn update(&mut self, data: &[u8]) {
+        let first_block = (self.length / 512) as usize;
+        self.length = (data.len() as u64)
+            .checked_mul(8)
+            .and_then(|bits| self.length.checked_add(bits))
+            .expect("SHA256 messages must be shorter than 2^64 bits!");
+
+        // Top up a partially filled buffer, then compress whole blocks straight from `data`
+        // so large inputs are never copied into the buffer.
+        let take = if self.buffer.is_empty() {
+            0
+        } else {
+            (64 - self.buffer.len()).min(data.len())
+        };
+        self.buffer.extend_from_slice(&data[..take]);
+        let data = &data[take..];
+        let full_len = data.len() - data.len() % 64;
+
+        if self.buffer.len() == 64 || full_len > 0 {
+            let K = round_constants();
+            let blocks = self
+                .buffer
+                .chunks_exact(64)
+                .chain(data[..full_len].chunks_exact(64));
+            for (i, block) in blocks.enumerate() {
+                let _span = block_span(first_block + i);
+                self.state = C::compress(self.state, &bytes_to_bits(block), K);
+            }
+            self.buffer.clear();
+        }
+        self.buffer.extend_from_slice(&data[full_len..]);
+    }
+
+    /// Pads the buffered bytes with the total message length and returns the final digest.
+    #[must_use]
+    pub fn finalize(mut self) -> Digest<F> {
+        self.finalize_reset()
+    }
+
+    /// Fused final step of a streamed verification: compresses the last buffered block and
+    /// compares the 32-byte digest with `expected` through [`constant_time_eq`], without a hex
+    /// round trip, so the caller never holds the digest itself.
+    #[must_use]
+    pub fn finalize_and_verify(self, expected: &[u8; 32]) -> bool {
+        constant_time_eq(&digest_to_bytes(self.finalize()), expected)
+    }
+
+    /// Returns the digest of everything absorbed so far and resets the state and buffer,
+    /// so the hasher can be reused for a fresh message.
+    #[must_use]
+    pub fn finalize_reset(&mut self) -> Digest<F> {
+        let first_block = (self.length / 512) as usize;
+        let mut bits = bytes_to_bits(&self.buffer);
+        bits.push(1);
+        while bits.len() % 512 != 448 {
+            bits.push(0);
+        }
+        bits.extend_from_slice(&to_bits_be::<_, 64>(self.length));
+
+        let K = round_constants();
+        for (i, chunk) in bits.chunks(512).enumerate() {
+            let _span = block_span(first_block + i);
+            self.state = C::compress(self.state, chunk, K);
+        }
+
+        let digest = self.state;
+        trace_digest(digest);
+        self.reset();
+        digest
+    }
+
+    /// Discards any absorbed data and restores the initial state.
+    pub fn reset(&mut self) {
+        self.state = initial_state();
+        self.buffer.clear();
+        self.length = 0;
+    }
+}
+
+/// Tests native SHA256 logic against Rust's standard `sha2` implementation.
+#[test]
+fn native_sha256_test() {
+    // === Test 1: SHA256 of a zero byte ===
+    let zero_bits = from_hex("00");
+    let (padded, _) = sha256_pad(zero_bits, 512).unwrap();
+    let zero_hash = NativeSha256::<Fp>::new(padded).hash();
+    // Output digest as hex string.
+    let zero_hash_hex = digest_to_hex(zero_hash);
+
+    // Standart Sha256.
+    let zero_std = Sha256::digest(&[0u8]);
+    let zero_std_hex = hex::encode(zero_std);
+
+    assert_eq!(zero_hash_hex, zero_std_hex, "Mismatch on 0x00.");
+
+    // === Test 2: SHA256 of a random field elements ===
+    let mut rng = tests::make_test_rng(None);
+    let first_random = Fp::rand(&mut rng);
+    let second_random = Fp::rand(&mut rng);
+    let first_random_hex = first_random.to_hex();
+    let second_random_hex = second_random.to_hex();
+    let concatenated = format!("{}{}", first_random_hex, second_random_hex);
+
+    let bits = from_hex(&concatenated);
+    let (padded, digest_index) = sha256_pad(bits, 1024).unwrap();
+    let hash_index = 960;
+    let native_hash = NativeSha256::<Fp>::new(padded).hash();
+    // Output digest as hex string.
+    let native_hash_hex = digest_to_hex(native_hash);
+
+    // Standart Sha256.
+    let bytes = hex::decode(&concatenated).unwrap();
+    let std_hash = Sha256::digest(&bytes);
+    let std_hash_hex = hex::encode(std_hash);
+
+    assert_eq!(
+        hash_index, digest_index,
+        "Mismatch between hash index and expected hash index."
+    );
+
+    assert_eq!(
+        native_hash_hex, std_hash_hex,
+        "Mismatch between native and standard SHA256."
+    );
+
+    // === Test 3: SHA256 of a random field elements ===
+    // Given random 4 Fp elements.
+    let rand_numbers = [
+        Fp::rand(&mut rng),
+        Fp::rand(&mut rng),
+        Fp::rand(&mut rng),
+        Fp::rand(&mut rng),
+    ];
+    let rand_numbers_hex: Vec<String> = rand_numbers.iter().map(|num| num.to_hex()).collect();
+    let merged_hex: String = rand_numbers_hex
+        .iter()
+        .flat_map(|num| num.chars())
+        .collect();
+
+    let bits = from_hex(&merged_hex);
+    let (padded, digest_index) = sha256_pad(bits, 1536).unwrap();
+    let hash_index = 1472;
+    let native_hash = NativeSha256::<Fp>::new(padded).hash();
+    // Output digest as hex string.
+    let native_hash_hex = digest_to_hex(native_hash);
+
+    // Standart Sha256.
+    let bytes = hex::decode(&merged_hex).unwrap();
+    let std_hash = Sha256::digest(&bytes);
+    let std_hash_hex = hex::encode(std_hash);
+
+    assert_eq!(
+        hash_index, digest_index,
+        "Mismatch between hash index and expected hash index."
+    );
+
+    assert_eq!(
+        native_hash_hex, std_hash_hex,
+        "Mismatch between native and standard SHA256."
+    );
+}
+
+/// Tests that the packed-byte path matches the bit path and the standard digest.
+#[test]
+fn hash_packed_test() {
+    let data = [0x5au8; 150];
+    let (packed, _) = sha256_pad_bytes(data.to_vec(), 192).unwrap();
+    let (bits, _) = sha256_pad(bytes_to_bits(&data), 1536).unwrap();
+
+    let digest = NativeSha256::<Fp>::hash_packed(&packed);
+    assert_eq!(
+        digest,
+        NativeSha256::<Fp>::new(bits).hash(),
+        "Mismatch between packed and bit paths."
+    );
+    assert_eq!(
+        digest_to_hex(digest),
+        hex::encode(Sha256::digest(data)),
+        "Mismatch between packed and standard SHA256."
+    );
+}
+
+/// Tests that the hash statistics are consistent with the padded preimage.
+#[test]
+fn hash_with_stats_test() {
+    for (len, max_bits) in [(0, 512), (440, 512), (448, 1024), (1000, 2048)] {
+        let (padded, _) = sha256_pad(vec![1u8; len], max_bits).unwrap();
+        let (digest, stats) = NativeSha256::<Fp>::new(padded.clone()).hash_with_stats();
+
+        assert_eq!(
+            digest,
+            NativeSha256::<Fp>::new(padded.clone()).hash(),
+            "Mismatch on digest."
+        );
+        assert_eq!(stats.blocks * 512, padded.len(), "Mismatch on blocks.");
+        assert_eq!(stats.input_bits, len, "Mismatch on input bits.");
+        assert_eq!(
+            stats.padding_bits,
+            max_bits - len,
+            "Mismatch on padding bits."
+        );
+    }
+}
+
+/// Tests that hashing raw bytes instead of bits fails with an actionable message.
+#[test]
+#[should_panic(expected = "did you forget bytes_to_bits?")]
+fn hash_rejects_bytes_test() {
+    // 64 real bytes: block-aligned as bits would be, but holding values above 1.
+    let bytes = b"The quick brown fox jumps over the lazy dog, and keeps running..".to_vec();
+    let _ = NativeSha256::<Fp>::new(bytes).hash();
+}
+
+/// Tests that streaming accepts the last byte the 64-bit length field can record, on a
+/// synthetic length.
+#[test]
+fn streaming_length_limit_test() {
+    let mut hasher = StreamingSha256::<Fp>::new();
+    hasher.length = u64::MAX - 15;
+    hasher.update(&[0]);
+    assert_eq!(
+        hasher.length,
+        u64::MAX - 7,
+        "Mismatch on the last valid length."
+    );
+}
+
+/// Tests that streaming past the 64-bit length field fails, on a synthetic length.
+#[test]
+#[should_panic(expected = "shorter than 2^64 bits")]
+fn streaming_length_overflow_test() {
+    let mut hasher = StreamingSha256::<Fp>::new();
+    hasher.length = u64::MAX - 15;
+    // The last byte that fits, then one too many.
+    hasher.update(&[0]);
+    hasher.update(&[0]);
+}
+
+/// Tests that both schedule strategies give the standard digest on single- and multi-block inputs.
+#[test]
+fn hash_with_strategy_test() {
+    for len in [0usize, 55, 56, 64, 200, 1000] {
+        let data: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+        let bits = bytes_to_bits(&data);
+        let max_bits = min_padded_bits(bits.len());
+        let (padded, _) = sha256_pad(bits, max_bits).unwrap();
+
+        let lazy =
+            NativeSha256::<Fp>::new(padded.clone()).hash_with_strategy(ScheduleStrategy::Lazy);
+        let eager = NativeSha256::<Fp>::new(padded).hash_with_strategy(ScheduleStrategy::Eager);
+        assert_eq!(lazy, eager, "Strategies disagree on {len} bytes.");
+        assert_eq!(
+            digest_to_hex(eager),
+            hex::encode(Sha256::digest(&data)),
+            "Mismatch with sha2 on {len} bytes."
+        );
+    }
+}
+
+/// Tests the one-pass SHA256/SHA224 digests against `sha2`.
+#[test]
+fn hash_both_variants_test() {
+    use sha2::Sha224;
+
+    for data in [&b""[..], b"abc", &[0x99u8; 130]] {
+        let (sha256, sha224) = NativeSha256::<Fp>::hash_both_variants(data);
+        let sha224_bytes: Vec<u8> = sha224
+            .iter()
+            .flat_map(|word| bits_to_u32(*word).to_be_bytes())
+            .collect();
+
+        assert_eq!(
+            digest_to_hex(sha256),
+            hex::encode(Sha256::digest(data)),
+            "Mismatch on SHA256 of {} bytes.",
+            data.len()
+        );
+        assert_eq!(
+            sha224_bytes,
+            Sha224::digest(data).to_vec(),
+            "Mismatch on SHA224 of {} bytes.",
+            data.len()
+        );
+    }
+}
+
+/// Tests the padded preimage dump on a known padded buffer.
+#[test]
+fn padded_preimage_hex_test() {
+    let (padded, _) = sha256_pad(from_hex("616263"), 512).unwrap();
+    let hasher = NativeSha256::<Fp>::new(padded.clone());
+
+    // "abc", the 0x80 marker, zero fill and the 24-bit length.
+    let expected = format!("61626380{}18", "00".repeat(59));
+    assert_eq!(
+        hasher.padded_preimage_hex(),
+        expected,
+        "Mismatch on padded dump."
+    );
+    assert_eq!(
+        bytes_to_bits(&bits_to_bytes(&padded)),
+        padded,
+        "Packing must invert bytes_to_bits."
+    );
+}
+
+/// Tests concatenated hashing against `sha2` of the joined bytes.
+#[test]
+fn hash_concat_test() {
+    let a = [0x01u8; 10];
+    let b = [0x02u8; 64];
+    let c = [0x03u8; 77];
+
+    for parts in [
+        &[][..],
+        &[&a[..]],
+        &[&a[..], &b, &c],
+        &[&c[..], &[], &a, &a],
+    ] {
+        assert_eq!(
+            digest_to_hex(NativeSha256::<Fp>::hash_concat(parts)),
+            hex::encode(Sha256::digest(parts.concat())),
+            "Mismatch on parts of lengths {:?}.",
+            parts.iter().map(|part| part.len()).collect::<Vec<_>>()
+        );
+    }
+}
+
+/// Tests the integer encodings against `sha2` of their big-endian bytes.
+#[test]
+fn hash_integers_test() {
+    let std_hex = |bytes: &[u8]| hex::encode(Sha256::digest(bytes));
+
+    for x in [0u64, 1, 0x0102_0304_0506_0708, u64::MAX] {
+        assert_eq!(
+            digest_to_hex(NativeSha256::<Fp>::hash_u64_be(x)),
+            std_hex(&x.to_be_bytes()),
+            "Mismatch on u64 {x}."
+        );
+    }
+    for x in [0u32, 0xdead_beef] {
+        assert_eq!(
+            digest_to_hex(NativeSha256::<Fp>::hash_u32_be(x)),
+            std_hex(&x.to_be_bytes()),
+            "Mismatch on u32 {x}."
+        );
+    }
+    for x in [-1i64, i64::MIN, 42] {
+        assert_eq!(
+            digest_to_hex(NativeSha256::<Fp>::hash_i64_be(x)),
+            std_hex(&x.to_be_bytes()),
+            "Mismatch on i64 {x}."
+        );
+    }
+}
+
+/// Tests hashing from a precomputed prefix against hashing the whole message.
+#[test]
+fn hash_with_prefix_test() {
+    let header = [0x17u8; 128];
+    let prefix = NativeSha256::<Fp>::precompute_prefix(&header).unwrap();
+
+    for suffix in [&b""[..], b"message", &[0xeeu8; 100]] {
+        assert_eq!(
+            NativeSha256::hash_with_prefix(&prefix, suffix),
+            NativeSha256::<Fp>::hash_bytes(&[&header[..], suffix].concat()),
+            "Mismatch on a {}-byte suffix.",
+            suffix.len()
+        );
+    }
+
+    assert_eq!(
+        NativeSha256::<Fp>::precompute_prefix(&header[..100]).err(),
+        Some(Sha256Error::PrefixNotBlockAligned { len: 100 }),
+        "Expected a prefix alignment error."
+    );
+}
+
+/// Tests that the final working variables fed forward reproduce the digest.
+#[test]
+fn hash_with_working_vars_test() {
+    let data = [0x77; 100];
+    let (padded, _) = sha256_pad(bytes_to_bits(&data), 1024).unwrap();
+    let (digest, working) = NativeSha256::<Fp>::new(padded.clone()).hash_with_working_vars();
+
+    assert_eq!(
+        digest_to_hex(digest),
+        hex::encode(Sha256::digest(data)),
+        "Mismatch with standard SHA256."
+    );
+    let before_last = NativeSha256::<Fp>::new(padded).hash_prefix(1);
+    let fed_forward: [[Fp; 32]; 8] =
+        std::array::from_fn(|i| wrapping_add(working[i], before_last[i]));
+    assert_eq!(
+        fed_forward, digest,
+        "Feed-forward must reproduce the digest."
+    );
+    assert_ne!(
+        working, digest,
+        "Working variables must precede the feed-forward."
+    );
+}
+
+/// Tests per-block chaining values against the prefix states and the full digest.
+#[test]
+fn block_digests_hex_test() {
+    let data = [0x33; 130];
+    let (padded, _) = sha256_pad(bytes_to_bits(&data), 1536).unwrap();
+    let states = NativeSha256::<Fp>::new(padded.clone()).block_digests_hex();
+
+    assert_eq!(states.len(), 3, "Expected one state per block.");
+    assert_eq!(
+        states.last().unwrap(),
+        &hex::encode(Sha256::digest(data)),
+        "Last state must be the digest."
+    );
+    for (i, state) in states.iter().enumerate() {
+        assert_eq!(
+            *state,
+            digest_to_hex(NativeSha256::<Fp>::new(padded.clone()).hash_prefix(i + 1)),
+            "Mismatch on the state after block {i}."
+        );
+    }
+}
+
+/// Tests that `hash_prefix` stops at the requested block and covers the full hash.
+#[test]
+fn hash_prefix_test() {
+    use crate::compression::compress_block;
+
+    let (padded, _) = sha256_pad(bytes_to_bits(&[0x42; 100]), 1024).unwrap();
+    let hasher = || NativeSha256::<Fp>::new(padded.clone());
+
+    assert_eq!(
+        hasher().hash_prefix(2),
+        hasher().hash(),
+        "Prefix over all blocks must equal the hash."
+    );
+    assert_eq!(
+        hasher().hash_prefix(0),
+        initial_state(),
+        "Empty prefix must be the initial state."
+    );
+
+    let K = round_constants();
+    assert_eq!(
+        hasher().hash_prefix(1),
+        compress_block(initial_state(), &padded[..512], K),
+        "Mismatch on the first-block midstate."
+    );
+}
+
+/// Tests the shape of the exported witness and the round transitions it records.
+#[test]
+fn export_witness_test() {
+    let (padded, _) = sha256_pad(bytes_to_bits(&[0x61; 64]), 1024).unwrap();
+    let rows = NativeSha256::<Fp>::new(padded.clone()).export_witness();
+
+    assert_eq!(rows.len(), 128, "Expected 64 rows per block.");
+    assert!(
+        rows.iter().all(|row| row.len() == 11 * 32),
+        "Every row must hold 11 words."
+    );
+
+    let initial = initial_state::<Fp>();
+    assert_eq!(
+        rows[0][..256],
+        *initial.as_flattened(),
+        "First row must start from the initial state."
+    );
+
+    // Round i + 1 starts with a = T1 + T2 and e = d + T1 of round i.
+    let word = |row: &[Fp], i: usize| -> [Fp; 32] { row[i * 32..(i + 1) * 32].try_into().unwrap() };
+    for pair in rows[..64].windows(2) {
+        let (T1, T2) = (word(&pair[0], 8), word(&pair[0], 9));
+        assert_eq!(word(&pair[1], 0), wrapping_add(T1, T2), "Mismatch on a.");
+        assert_eq!(
+            word(&pair[1], 4),
+            wrapping_add(word(&pair[0], 3), T1),
+            "Mismatch on e."
+        );
+    }
+
+    assert_eq!(
+        digest_to_hex(NativeSha256::<Fp>::new(padded).hash()),
+        hex::encode(Sha256::digest([0x61; 64])),
+        "Witness export must not disturb the hash."
+    );
+}
+
+/// Tests that repeated hashing, configured hashers and, with the `serde` feature, a serialized
+/// midstate all reproduce byte-identical digests.
+#[test]
+fn determinism_test() {
+    use crate::builder::Sha256Builder;
+
+    let data = [0x5a; 150];
+    let reference = digest_to_bytes(NativeSha256::<Fp>::hash_bytes(&data));
+    assert_eq!(
+        digest_to_bytes(NativeSha256::<Fp>::hash_bytes(&data)),
+        reference,
+        "Repeated one-shot hashes differ."
+    );
+
+    // Custom IV and reduced rounds.
+    let configured = || {
+        let mut state = initial_state::<Fp>();
+        state[2][5] = Fp::from(1u8) - state[2][5];
+        Sha256Builder::new()
+            .init_state(state)
+            .rounds(20)
+            .build()
+            .unwrap()
+            .hash_bytes(&data)
+    };
+    assert_eq!(configured(), configured(), "Configured hashes differ.");
+
+    // Midstate round-tripped through a serde snapshot after two of the three blocks.
+    #[cfg(feature = "serde")]
+    {
+        use crate::dynamic_sha256::DynamicSha256;
+
+        let bits = bytes_to_bits(&data);
+        let (padded, digest_index) = sha256_pad(bits.clone(), min_padded_bits(bits.len())).unwrap();
+        let mut hasher = DynamicSha256::<Fp>::new(padded, digest_index, None);
+        assert!(hasher.compress_next_block(), "First block not compressed.");
+        assert!(hasher.compress_next_block(), "Second block not compressed.");
+
+        let json = serde_json::to_string(&hasher).unwrap();
+        let restored: DynamicSha256<Fp> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            serde_json::to_string(&restored).unwrap(),
+            json,
+            "Midstate changed in serialization."
+        );
+        assert_eq!(
+            digest_to_bytes(restored.hash()),
+            reference,
+            "Restored midstate hashes differently."
+        );
+    }
+}
+
+/// Tests hashing bytes in either bit order.
+#[test]
+fn hash_bytes_ordered_test() {
+    let data = b"abc";
+    assert_eq!(
+        digest_to_hex(NativeSha256::<Fp>::hash_bytes_ordered(data, BitOrder::Msb0)),
+        hex::encode(Sha256::digest(data)),
+        "Msb0 must match standard SHA256."
+    );
+
+    // Legacy reference: SHA256 over the bit-reversed bytes of "abc".
+    let lsb0 = NativeSha256::<Fp>::hash_bytes_ordered(data, BitOrder::Lsb0);
+    assert_eq!(
+        digest_to_hex(lsb0),
+        "5aa5cff7a4a9d4ce6b66a12aa390f1e547045917ec923cdc15d083e901e08c64",
+        "Mismatch on the Lsb0 legacy vector."
+    );
+    assert_eq!(
+        digest_to_hex(lsb0),
+        hex::encode(Sha256::digest(data.map(u8::reverse_bits))),
+        "Lsb0 must equal SHA256 of the bit-reversed bytes."
+    );
+}
+
+/// Tests that a trace filter records only the selected block.
+#[test]
+fn hash_with_trace_filter_test() {
+    let data = [0x62; 150];
+    let (padded, _) = sha256_pad(bytes_to_bits(&data), 1536).unwrap();
+
+    let (digest, traces) = NativeSha256::<Fp>::new(padded.clone()).hash_with_trace_filter(&[1, 7]);
+    assert_eq!(
+        digest_to_hex(digest),
+        hex::encode(Sha256::digest(data)),
+        "Filtering must not disturb the hash."
+    );
+    assert_eq!(traces.len(), 1, "Expected exactly one trace.");
+
+    let (index, trace) = &traces[0];
+    assert_eq!(*index, 1, "Mismatch on the traced block index.");
+    assert_eq!(
+        trace.schedule,
+        message_schedule::<Fp>(&padded[512..1024]),
+        "Mismatch on the traced schedule."
+    );
+    assert_eq!(trace.rounds.len(), 64, "Expected 64 traced rounds.");
+    assert_eq!(
+        trace.rounds[0].working,
+        NativeSha256::<Fp>::new(padded.clone()).hash_prefix(1),
+        "Trace must start from the midstate after block 0."
+    );
+    assert_eq!(
+        trace.output,
+        NativeSha256::<Fp>::new(padded).hash_prefix(2),
+        "Mismatch on the traced block output."
+    );
+}
+
+/// Tests byte verification of a padded preimage, with mismatches at either end of the digest.
+#[test]
+fn verify_bytes_test() {
+    let data = b"verify me";
+    let expected: [u8; 32] = Sha256::digest(data).into();
+    let bits = bytes_to_bits(data);
+    let (padded, _) = sha256_pad(bits.clone(), min_padded_bits(bits.len())).unwrap();
+    let hasher = || NativeSha256::<Fp>::new(padded.clone());
+
+    assert!(
+        hasher().verify_bytes(&expected),
+        "Matching digest rejected."
+    );
+
+    // Only checks that a difference at either end is caught. The absence of an early exit is
+    // not measured here; it rests on `constant_time_eq` folding over every byte.
+    for index in [0, 31] {
+        let mut wrong = expected;
+        wrong[index] ^= 1;
+        assert!(
+            !hasher().verify_bytes(&wrong),
+            "Mismatch at byte {index} accepted."
+        );
+    }
+}
+
+/// Tests that extra capacity blocks from `sha256_pad` are compressed and change the digest.
+#[test]
+fn extra_padding_blocks_test() {
+    use crate::compression::compress_block;
+
+    let bits = bytes_to_bits(b"abc");
+    let (minimal, _) = sha256_pad(bits.clone(), 512).unwrap();
+    let (extended, _) = sha256_pad(bits, 1024).unwrap();
+    assert_eq!(
+        padded_message_bits(&minimal),
+        padded_message_bits(&extended),
+        "Both paddings must encode the same message."
+    );
+
+    let standard = NativeSha256::<Fp>::new(minimal.clone()).hash();
+    assert_eq!(
+        digest_to_hex(standard),
+        hex::encode(Sha256::digest(b"abc")),
+        "Minimal padding must give the standard digest."
+    );
+
+    // The zero block is a real block: the result is the standard state compressed once more.
+    let extended_hash = NativeSha256::<Fp>::new(extended).hash();
+    assert_ne!(
+        extended_hash, standard,
+        "An extra zero block must change the digest."
+    );
+    assert_eq!(
+        extended_hash,
+        compress_block(standard, &[0u8; 512], round_constants()),
+        "Mismatch on the extra zero block compression."
+    );
+}
+
+/// Tests fused finalization and verification of a multi-block streamed message.
+#[test]
+fn finalize_and_verify_test() {
+    let data: Vec<u8> = (0..=255u8).cycle().take(300).collect();
+    let expected: [u8; 32] = Sha256::digest(&data).into();
+    let hasher = || {
+        let mut hasher = StreamingSha256::<Fp>::new();
+        for piece in data.chunks(37) {
+            hasher.update(piece);
+        }
+        hasher
+    };
+
+    assert!(
+        hasher().finalize_and_verify(&expected),
+        "Matching digest rejected."
+    );
+
+    // Mismatches at either end and in the middle are caught. The absence of an early exit is
+    // not measured here; it rests on `constant_time_eq` folding over every byte.
+    for index in [0, 15, 31] {
+        let mut wrong = expected;
+        wrong[index] ^= 0x80;
+        assert!(
+            !hasher().finalize_and_verify(&wrong),
+            "Mismatch at byte {index} accepted."
+        );
+    }
+}
+
+/// Tests that `finalize_reset` matches independent one-shot hashes for consecutive messages.
+#[test]
+fn finalize_reset_test() {
+    let first = hex::decode("00").unwrap();
+    let second = b"The quick brown fox jumps over the lazy dog, twice over to span blocks.";
+
+    let mut hasher = StreamingSha256::<Fp>::new();
+    hasher.update(&first);
+    let first_streamed = hasher.finalize_reset();
+    hasher.update(second);
+    let second_streamed = hasher.finalize_reset();
+
+    let (padded, _) = sha256_pad(bytes_to_bits(&first), 512).unwrap();
+    let first_hash = NativeSha256::<Fp>::new(padded).hash();
+    let (padded, _) = sha256_pad(bytes_to_bits(second), 1024).unwrap();
+    let second_hash = NativeSha256::<Fp>::new(padded).hash();
+
+    assert_eq!(
+        digest_to_hex(first_streamed),
+        digest_to_hex(first_hash),
+        "Mismatch on first message after finalize_reset."
+    );
+    assert_eq!(
+        digest_to_hex(second_streamed),
+        digest_to_hex(second_hash),
+        "Mismatch on second message after finalize_reset."
+    );
+    assert_eq!(
+        digest_to_hex(second_streamed),
+        hex::encode(Sha256::digest(second)),
+        "Mismatch between streamed and standard SHA256."
+    );
+}
+
+/// Tests that tracing emits one `process_chunk` span per block without changing the digest.
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_test() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tracing::{span, Event, Metadata, Subscriber};
+
+    #[derive(Clone, Default)]
+    struct Counter {
+        spans: Arc<AtomicUsize>,
+        events: Arc<AtomicUsize>,
+    }
+
+    impl Subscriber for Counter {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+            if attrs.metadata().name() == "process_chunk" {
+                self.spans.fetch_add(1, Ordering::SeqCst);
+            }
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, _: &Event<'_>) {
+            self.events.fetch_add(1, Ordering::SeqCst);
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let message = b"The quick brown fox jumps over the lazy dog, twice over to span blocks.";
+    let counter = Counter::default();
+    let (one_shot, streamed) = tracing::subscriber::with_default(counter.clone(), || {
+        let (padded, _) = sha256_pad(bytes_to_bits(message), 1024).unwrap();
+        let one_shot = NativeSha256::<Fp>::new(padded).hash();
+        let streamed = NativeSha256::<Fp>::hash_bytes(message);
+        (one_shot, streamed)
+    });
+
+    assert_eq!(
+        counter.spans.load(Ordering::SeqCst),
+        4,
+        "Expected one span per block."
+    );
+    assert_eq!(
+        counter.events.load(Ordering::SeqCst),
+        2,
+        "Expected one digest event per hash."
+    );
+
+    let std_hash_hex = hex::encode(Sha256::digest(message));
+    assert_eq!(
+        digest_to_hex(one_shot),
+        std_hash_hex,
+        "Mismatch with tracing enabled."
+    );
+    assert_eq!(
+        digest_to_hex(streamed),
+        std_hash_hex,
+        "Mismatch with tracing enabled."
+    );
+}
+
+/// Tests the byte limit boundary of `hash_bounded`.
+#[test]
+fn hash_bounded_test() {
+    let data = [0x61u8; 100];
+
+    let hash = NativeSha256::<Fp>::hash_bounded(&data, 100).unwrap();
+    assert_eq!(
+        digest_to_hex(hash),
+        hex::encode(Sha256::digest(data)),
+        "Mismatch on input of exactly max_bytes."
+    );
+
+    assert_eq!(
+        NativeSha256::<Fp>::hash_bounded(&data, 99),
+        Err(Sha256Error::InputTooLong {
+            len: 100,
+            max_bytes: 99
+        }),
+        "Expected an input too long error."
+    );
+}
+
+/// Tests that `hash_128` is the 16-byte prefix of the standard digest.
+#[test]
+fn hash_128_test() {
+    for data in [&b""[..], b"abc", &[0x5a; 200]] {
+        assert_eq!(
+            NativeSha256::<Fp>::hash_128(data)[..],
+            Sha256::digest(data)[..16],
+            "Mismatch on truncated digest."
+        );
+    }
+}
+
+/// Tests `hash_bitslice` against `hash_bytes` and the bit-level padding pipeline.
+#[cfg(feature = "bitvec")]
+#[test]
+fn hash_bitslice_test() {
+    use bitvec::prelude::*;
+
+    // === Test 1: Byte-aligned Msb0 slices match hash_bytes ===
+    let data = b"bit slices and bytes agree";
+    let hash = NativeSha256::<Fp>::hash_bitslice(data.view_bits::<Msb0>());
+    assert_eq!(
+        digest_to_hex(hash),
+        digest_to_hex(NativeSha256::<Fp>::hash_bytes(data)),
+        "Mismatch on Msb0 slice."
+    );
+
+    // === Test 2: Lsb0 slices are hashed in their own bit order ===
+    let reversed: Vec<u8> = data.iter().map(|byte| byte.reverse_bits()).collect();
+    let hash = NativeSha256::<Fp>::hash_bitslice(data.view_bits::<Lsb0>());
+    assert_eq!(
+        digest_to_hex(hash),
+        digest_to_hex(NativeSha256::<Fp>::hash_bytes(&reversed)),
+        "Mismatch on Lsb0 slice."
+    );
+
+    // === Test 3: Non-aligned slices match the bit-level oracle ===
+    let bits = &data.view_bits::<Msb0>()[..13];
+    let oracle_bits = bytes_to_bits(data)[..13].to_vec();
+    let (padded, _) = sha256_pad(oracle_bits, 512).unwrap();
+    assert_eq!(
+        digest_to_hex(NativeSha256::<Fp>::hash_bitslice(bits)),
+        digest_to_hex(NativeSha256::<Fp>::new(padded).hash()),
+        "Mismatch on non-aligned slice."
+    );
+}
+
+/// Tests a 1 MB pseudo-random input against Rust's standard `sha2` implementation.
+/// Slow in debug builds; run with `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn large_input_test() {
+    use rand::RngCore;
+
+    let mut rng = tests::make_test_rng(None);
+    let mut data = vec![0u8; 1 << 20];
+    rng.fill_bytes(&mut data);
+
+    assert_eq!(
+        digest_to_hex(NativeSha256::<Fp>::hash_bytes(&data)),
+        hex::encode(Sha256::digest(&data)),
+        "Mismatch on 1 MB input."
+    );
+}
+
+/// Tests the FIPS 180-2 "one million a's" vector, streamed in uneven chunks so that the
+/// input is never held in memory. Slow in debug builds; run with `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn million_a_test() {
+    let chunk = [b'a'; 1000];
+    let mut hasher = StreamingSha256::<Fp>::new();
+    let mut remaining = 1_000_000;
+    for len in [1, 63, 64, 999].into_iter().cycle() {
+        let len = len.min(remaining);
+        hasher.update(&chunk[..len]);
+        remaining -= len;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    assert_eq!(
+        digest_to_hex(hasher.finalize()),
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+        "Mismatch on one million a's."
+    );
+}
+
+/// Tests that hashing scattered fragments equals hashing the coalesced buffer.
+#[test]
+fn hash_vectored_test() {
+    let data: Vec<u8> = (0..300u32).map(|i| (i * 7) as u8).collect();
+    // Fragment boundaries mid-block, on block boundaries, and empty fragments.
+    for cuts in [
+        vec![],
+        vec![0],
+        vec![10, 100],
+        vec![63, 64, 65],
+        vec![1, 2, 130, 130, 299],
+    ] {
+        let mut bounds = vec![0];
+        bounds.extend(cuts);
+        bounds.push(data.len());
+        let fragments: Vec<IoSlice> = bounds
+            .windows(2)
+            .map(|w| IoSlice::new(&data[w[0]..w[1]]))
+            .collect();
+        assert_eq!(
+            digest_to_hex(NativeSha256::<Fp>::hash_vectored(&fragments)),
+            hex::encode(Sha256::digest(&data)),
+            "Mismatch with fragment bounds {bounds:?}."
+        );
+    }
+    assert_eq!(
+        NativeSha256::<Fp>::hash_vectored(&[]),
+        NativeSha256::<Fp>::hash_bytes(&[]),
+        "Mismatch on no fragments."
+    );
+}
+
+/// Tests that the differential check against `sha2` passes with the standard compressor and
+/// catches a broken one, in both engines.
+#[test]
+fn compressor_test() {
+    use crate::{compression::BrokenCompressor, dynamic_sha256::DynamicSha256};
+
+    for data in [&b""[..], b"abc", &[0x5a; 100]] {
+        let expected = hex::encode(Sha256::digest(data));
+        let bits = bytes_to_bits(data);
+        let (padded, digest_index) = sha256_pad(bits.clone(), min_padded_bits(bits.len())).unwrap();
+
+        let mut standard = StreamingSha256::<Fp, Sha256Compressor>::with_compressor();
+        standard.update(data);
+        assert_eq!(
+            digest_to_hex(standard.finalize()),
+            expected,
+            "Standard compressor diverges."
+        );
+        let mut broken = StreamingSha256::<Fp, BrokenCompressor>::with_compressor();
+        broken.update(data);
+        assert_ne!(
+            digest_to_hex(broken.finalize()),
+            expected,
+            "Broken streaming compressor not detected."
+        );
+        let broken = || NativeSha256::<Fp, BrokenCompressor>::with_compressor(padded.clone());
+        let broken_digest = broken().hash();
+        assert_ne!(
+            digest_to_hex(broken_digest),
+            expected,
+            "Broken one-shot compressor not detected."
+        );
+
+        // Inspection must compress with the same compressor as the plain hash.
+        assert_eq!(
+            broken().hash_with_working_vars().0,
+            broken_digest,
+            "Working-variable hash bypasses the compressor."
+        );
+        assert_eq!(
+            broken().hash_with_trace_filter(&[0]).0,
+            broken_digest,
+            "Traced hash bypasses the compressor."
+        );
+        assert_eq!(
+            broken().export_witness().len(),
+            padded.len() / 512 * 63,
+            "Witness export bypasses the compressor."
+        );
+
+        let dynamic = |padded| {
+            DynamicSha256::<Fp, BrokenCompressor>::try_with_compressor(padded, digest_index, None)
+                .unwrap()
+                .hash()
+        };
+        assert_ne!(
+            digest_to_hex(dynamic(padded)),
+            expected,
+            "Broken dynamic compressor not detected."
+        );
+    }
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    // Each case hashes up to five blocks twice in the field, so keep the case count modest.
+    #![proptest_config(proptest::prelude::ProptestConfig::with_cases(32))]
+
+    /// Tests that splitting the input at random positions, with empty chunks in between, does
+    /// not change the streamed digest.
+    #[test]
+    fn streaming_chunks_test(
+        data in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..300),
+        cuts in proptest::collection::vec(proptest::prelude::any::<proptest::sample::Index>(), 0..16),
+        empties: u16,
+    ) {
+        let mut bounds: Vec<usize> = cuts.iter().map(|cut| cut.index(data.len() + 1)).collect();
+        bounds.extend([0, data.len()]);
+        bounds.sort_unstable();
+
+        let mut hasher = StreamingSha256::<Fp>::new();
+        for (i, window) in bounds.windows(2).enumerate() {
+            if empties >> (i % 16) & 1 == 1 {
+                hasher.update(&[]);
+            }
+            hasher.update(&data[window[0]..window[1]]);
+        }
+        proptest::prop_assert_eq!(
+            hasher.finalize(),
+            NativeSha256::<Fp>::hash_bytes(&data),
+            "Mismatch when streamed in {} pieces.",
+            bounds.len() - 1
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Merkle–Damgård hashing in the shape of SHA256 with every dimension as a parameter, for
+//! teaching and experiments.
+//!
+//! [`Params`] collects what the SHA256 code fixes: word width, block and length-field sizes,
+//! round count, rotation amounts, schedule taps and constants. [`Sha256Params`] recovers the
+//! standard hash; [`ToyParams`] shrinks it to 8-bit words and 64-bit blocks, small enough to
+//! follow round by round on paper.
+//!
+//! This is the one implementation of the field-element compression: the SHA256 functions in
+//! [`crate::compression`] instantiate it with [`Sha256Params`].
+
+#![allow(non_snake_case)]
+
+use ark_ff::PrimeField;
+
+use crate::{
+    compression::RoundTrace,
+    constants::{initial_state_u32, round_constants_u32},
+    sha_helpers::*,
+};
+
+/// Dimensions and constants of a SHA256-shaped hash over `W`-bit words.
+pub trait Params<const W: usize> {
+    /// Message words per block; a block has `BLOCK_WORDS * W` bits.
+    const BLOCK_WORDS: usize;
+    /// Width of the message length field that closes the padding, at most 64 bits.
+    const LENGTH_BITS: usize;
+    /// Rounds per block. The message schedule has `max(ROUNDS, BLOCK_WORDS)` words.
+    const ROUNDS: usize;
+    /// Rotations of `Σ0`, applied to `a` in every round.
+    const BIG_SIGMA0: [usize; 3];
+    /// Rotations of `Σ1`, applied to `e` in every round.
+    const BIG_SIGMA1: [usize; 3];
+    /// Two rotations and a right shift making up the schedule function `σ0`.
+    const SMALL_SIGMA0: [usize; 3];
+    /// Two rotations and a right shift making up the schedule function `σ1`.
+    const SMALL_SIGMA1: [usize; 3];
+    /// Schedule taps `[p, q, r, s]`, none above `BLOCK_WORDS`:
+    /// `W[i] = σ1(W[i - p]) + W[i - q] + σ0(W[i - r]) + W[i - s]`.
+    const SCHEDULE_TAPS: [usize; 4];
+
+    /// Initial chaining state, one integer per word.
+    fn initial_state() -> [u64; 8];
+
+    /// Round constants, one integer per round.
+    fn round_constants() -> Vec<u64>;
+}
+
+/// Standard SHA256 (FIPS 180-4).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sha256Params;
+
+impl Params<32> for Sha256Params {
+    const BLOCK_WORDS: usize = 16;
+    const LENGTH_BITS: usize = 64;
+    const ROUNDS: usize = 64;
+    const BIG_SIGMA0: [usize; 3] = [2, 13, 22];
+    const BIG_SIGMA1: [usize; 3] = [6, 11, 25];
+    const SMALL_SIGMA0: [usize; 3] = [7, 18, 3];
+    const SMALL_SIGMA1: [usize; 3] = [17, 19, 10];
+    const SCHEDULE_TAPS: [usize; 4] = [2, 7, 15, 16];
+
+    fn initial_state() -> [u64; 8] {
+        initial_state_u32().map(u64::from)
+    }
+
+    fn round_constants() -> Vec<u64> {
+        round_constants_u32().map(u64::from).to_vec()
+    }
+}
+
+/// Toy SHA256 for teaching: 8-bit words, 64-bit blocks, a 16-bit length field and 16 rounds.
+/// The constants are the leading bytes of the SHA256 ones. It has no security whatsoever.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ToyParams;
+
+impl Params<8> for ToyParams {
+    const BLOCK_WORDS: usize = 8;
+    const LENGTH_BITS: usize = 16;
+    const ROUNDS: usize = 16;
+    const BIG_SIGMA0: [usize; 3] = [2, 3, 5];
+    const BIG_SIGMA1: [usize; 3] = [1, 4, 6];
+    const SMALL_SIGMA0: [usize; 3] = [1, 6, 2];
+    const SMALL_SIGMA1: [usize; 3] = [3, 5, 1];
+    const SCHEDULE_TAPS: [usize; 4] = [1, 3, 7, 8];
+
+    fn initial_state() -> [u64; 8] {
+        initial_state_u32().map(|word| u64::from(word >> 24))
+    }
+
+    fn round_constants() -> Vec<u64> {
+        round_constants_u32()[..Self::ROUNDS]
+            .iter()
+            .map(|word| u64::from(word >> 24))
+            .collect()
+    }
+}
+
+/// Converts an integer into a `W`-bit word of field elements.
+fn word<F: PrimeField, const W: usize>(x: u64) -> [F; W] {
+    bits_to_field(&to_bits_be::<_, W>(x))
+}
+
+/// Pads a bit message with the `1` marker, zero fill and the `LENGTH_BITS`-bit length field to
+/// a whole number of blocks.
+/// Panics if the message length does not fit in the length field.
+pub fn pad<const W: usize, P: Params<W>>(input_bits: Vec<u8>) -> Vec<u8> {
+    assert!(P::LENGTH_BITS <= 64, "The length field is at most 64 bits!");
+    let length = input_bits.len() as u64;
+    assert!(
+        P::LENGTH_BITS == 64 || length >> P::LENGTH_BITS == 0,
+        "Message too long for the length field!"
+    );
+
+    let block_bits = P::BLOCK_WORDS * W;
+    let mut padded = input_bits;
+    padded.push(1);
+    while padded.len() % block_bits != block_bits - P::LENGTH_BITS {
+        padded.push(0);
+    }
+    padded.extend((0..P::LENGTH_BITS).rev().map(|i| ((length >> i) & 1) as u8));
+    padded
+}
+
+/// Expands one block into the message schedule of `P`, `max(ROUNDS, BLOCK_WORDS)` words.
+#[must_use]
+pub fn message_schedule<F: PrimeField, const W: usize, P: Params<W>>(block: &[u8]) -> Vec<[F; W]> {
+    assert_eq!(block.len(), P::BLOCK_WORDS * W, "Block size mismatch!");
+
+    let mut schedule: Vec<[F; W]> = block.chunks_exact(W).map(bits_to_field).collect();
+    schedule.resize(P::ROUNDS.max(P::BLOCK_WORDS), [F::zero(); W]);
+    expand_schedule::<F, W, P>(&mut schedule);
+    schedule
+}
+
+/// Fills in `schedule` after its first `BLOCK_WORDS` words, which hold the block, with the
+/// schedule recurrence of `P`. Works in place so callers can keep the schedule on the stack.
+pub(crate) fn expand_schedule<F: PrimeField, const W: usize, P: Params<W>>(
+    schedule: &mut [[F; W]],
+) {
+    // Shadow the helpers with variants sharing one set of field constants.
+    let consts = FieldConsts::new();
+    let xor = |x: [F; W], y: [F; W]| xor_with(&consts, x, y);
+    let add = |x: [F; W], y: [F; W]| ripple_add(&consts, x, y);
+    let small_sigma = |rot: [usize; 3], x: [F; W]| {
+        xor(
+            xor(rotate_right(rot[0], x), rotate_right(rot[1], x)),
+            right_shift(rot[2], x),
+        )
+    };
+
+    let [p, q, r, s] = P::SCHEDULE_TAPS;
+    for i in P::BLOCK_WORDS..schedule.len() {
+        schedule[i] = add(
+            add(
+                small_sigma(P::SMALL_SIGMA1, schedule[i - p]),
+                schedule[i - q],
+            ),
+            add(
+                small_sigma(P::SMALL_SIGMA0, schedule[i - r]),
+                schedule[i - s],
+            ),
+        );
+    }
+}
+
+/// Runs the first `rounds` rounds of `P` over an expanded schedule with the round constants `K`,
+/// reporting each round to `on_round`. Returns the working variables `a..h` before the
+/// feed-forward addition.
+pub(crate) fn run_rounds<F: PrimeField, const W: usize, P: Params<W>>(
+    state: [[F; W]; 8],
+    schedule: &[[F; W]],
+    K: &[[F; W]],
+    rounds: usize,
+    mut on_round: impl FnMut(RoundTrace<F, W>),
+) -> [[F; W]; 8] {
+    let consts = FieldConsts::new();
+    let xor = |x: [F; W], y: [F; W]| xor_with(&consts, x, y);
+    let add = |x: [F; W], y: [F; W]| ripple_add(&consts, x, y);
+    let big_sigma = |rot: [usize; 3], x: [F; W]| {
+        xor(
+            xor(rotate_right(rot[0], x), rotate_right(rot[1], x)),
+            rotate_right(rot[2], x),
+        )
+    };
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+    for i in 0..rounds {
+        let Ch = xor(and(e, f), and(not_with(&consts, e), g));
+        let T1 = add(
+            add(add(add(h, big_sigma(P::BIG_SIGMA1, e)), Ch), K[i]),
+            schedule[i],
+        );
+        let Maj = xor(xor(and(a, b), and(a, c)), and(b, c));
+        let T2 = add(big_sigma(P::BIG_SIGMA0, a), Maj);
+
+        on_round(RoundTrace {
+            working: [a, b, c, d, e, f, g, h],
+            T1,
+            T2,
+            W: schedule[i],
+        });
+
+        h = g;
+        g = f;
+        f = e;
+        e = add(d, T1);
+        d = c;
+        c = b;
+        b = a;
+        a = add(T1, T2);
+    }
+
+    [a, b, c, d, e, f, g, h]
+}
+
+/// Applies the compression function of `P` to one block, including the feed-forward addition.
+pub fn compress<F: PrimeField, const W: usize, P: Params<W>>(
+    state: [[F; W]; 8],
+    block: &[u8],
+) -> [[F; W]; 8] {
+    let schedule = message_schedule::<F, W, P>(block);
+    let K: Vec<[F; W]> = P::round_constants().into_iter().map(word).collect();
+    let working = run_rounds::<F, W, P>(state, &schedule, &K, P::ROUNDS, |_| {});
+
+    let consts = FieldConsts::new();
+    std::array::from_fn(|i| ripple_add(&consts, working[i], state[i]))
+}
+
+/// Pads a bit message with [`pad`] and hashes it block by block with [`compress`].
+#[must_use]
+pub fn hash<F: PrimeField, const W: usize, P: Params<W>>(input_bits: Vec<u8>) -> [[F; W]; 8] {
+    let padded = pad::<W, P>(input_bits);
+    padded
+        .chunks_exact(P::BLOCK_WORDS * W)
+        .fold(P::initial_state().map(word), |state, block| {
+            compress::<F, W, P>(state, block)
+        })
+}
+
+/// Tests that the generic core instantiated with the SHA256 parameters is SHA256.
+#[test]
+fn sha256_params_test() {
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    for data in [&b"abc"[..], &[0x33; 100]] {
+        assert_eq!(
+            digest_to_hex(hash::<Fp, 32, Sha256Params>(bytes_to_bits(data))),
+            hex::encode(Sha256::digest(data)),
+            "Mismatch with standard SHA256 on {} bytes.",
+            data.len()
+        );
+    }
+}
+
+/// Tests that the toy hash is deterministic and that every input bit affects the output.
+#[test]
+fn toy_params_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let message = bytes_to_bits(b"Merkle");
+    assert_eq!(
+        pad::<8, ToyParams>(message.clone()).len(),
+        128,
+        "48 bits plus marker and length field take two 64-bit blocks."
+    );
+
+    let digest = hash::<Fp, 8, ToyParams>(message.clone());
+    assert_eq!(
+        digest,
+        hash::<Fp, 8, ToyParams>(message.clone()),
+        "Toy hash must be deterministic."
+    );
+
+    for i in 0..message.len() {
+        let mut flipped = message.clone();
+        flipped[i] ^= 1;
+        assert_ne!(
+            hash::<Fp, 8, ToyParams>(flipped),
+            digest,
+            "Flipping bit {i} left the digest unchanged."
+        );
+    }
+}
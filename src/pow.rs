@@ -0,0 +1,63 @@
+use ark_ff::PrimeField;
+
+use crate::{digest::Digest, native_sha256::StreamingSha256};
+
+/// Checks that the top `n` bits of the digest (big-endian, word 0 first) are all zero.
+#[must_use]
+pub fn has_leading_zero_bits<F: PrimeField>(digest: Digest<F>, n: usize) -> bool {
+    assert!(n <= 256, "A SHA256 digest only has 256 bits!");
+    digest.iter().flatten().take(n).all(|bit| *bit == F::zero())
+}
+
+/// Proof-of-work search: increments a nonce from 0 until `SHA256(prefix || nonce)` has
+/// `difficulty` leading zero bits, where the nonce is appended as 8 big-endian bytes.
+/// Returns the winning nonce and its digest.
+#[must_use]
+pub fn mine<F: PrimeField>(prefix: &[u8], difficulty: usize) -> (u64, Digest<F>) {
+    for nonce in 0u64.. {
+        let mut hasher = StreamingSha256::new();
+        hasher.update(prefix);
+        hasher.update(&nonce.to_be_bytes());
+        let digest = hasher.finalize();
+        if has_leading_zero_bits(digest, difficulty) {
+            return (nonce, digest);
+        }
+    }
+    unreachable!("Nonce space exhausted!")
+}
+
+/// Tests leading zero bit detection and a small proof-of-work search.
+#[test]
+fn pow_test() {
+    use crate::sha_helpers::*;
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    // === Test 1: Crafted digest with exactly 12 leading zero bits ===
+    let mut bytes = [0xffu8; 32];
+    bytes[0] = 0x00;
+    bytes[1] = 0x0f;
+    let digest = bytes_to_digest::<Fp>(&bytes);
+    assert!(
+        has_leading_zero_bits(digest, 0),
+        "Zero difficulty must pass."
+    );
+    assert!(
+        has_leading_zero_bits(digest, 12),
+        "Expected 12 leading zero bits."
+    );
+    assert!(
+        !has_leading_zero_bits(digest, 13),
+        "Expected only 12 leading zero bits."
+    );
+
+    // === Test 2: Mined nonce satisfies the difficulty ===
+    let (nonce, digest) = mine::<Fp>(b"block header", 6);
+    let std_hash = Sha256::digest([&b"block header"[..], &nonce.to_be_bytes()].concat());
+    assert_eq!(
+        digest_to_hex(digest),
+        hex::encode(std_hash),
+        "Mismatch on mined digest."
+    );
+    assert!(std_hash[0] >> 2 == 0, "Mined digest misses the difficulty.");
+}
@@ -0,0 +1,110 @@
+//! Plain `u32` SHA256 for integrity checks, with none of the field machinery.
+//!
+//! Words are native integers and padding is laid out on the stack, so this is the fast path
+//! for callers who only need a checksum. It is still standard SHA256, digest for digest equal
+//! to the field engines, but it has no circuit counterpart.
+
+#![allow(non_snake_case)]
+
+use crate::constants::{initial_state_u32, round_constants_u32};
+
+/// Namespace of the quick `u32` SHA256.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sha256;
+
+impl Sha256 {
+    /// Hashes `data` with plain `u32` arithmetic and returns the 32-byte digest.
+    #[must_use]
+    pub fn quick(data: &[u8]) -> [u8; 32] {
+        let mut state = initial_state_u32();
+
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            compress(&mut state, block.try_into().unwrap());
+        }
+
+        // The tail, the 0x80 marker and the 64-bit bit length fill one or two more blocks.
+        let tail = blocks.remainder();
+        let mut last = [0u8; 128];
+        last[..tail.len()].copy_from_slice(tail);
+        last[tail.len()] = 0x80;
+        let end = if tail.len() < 56 { 64 } else { 128 };
+        last[end - 8..end].copy_from_slice(&(8 * data.len() as u64).to_be_bytes());
+        for block in last[..end].chunks_exact(64) {
+            compress(&mut state, block.try_into().unwrap());
+        }
+
+        let mut digest = [0u8; 32];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+/// Applies the SHA256 compression function to a 64-byte block.
+fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+    let mut W = [0u32; 64];
+    for (i, bytes) in block.chunks_exact(4).enumerate() {
+        W[i] = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for i in 16..64 {
+        let s0 = W[i - 15].rotate_right(7) ^ W[i - 15].rotate_right(18) ^ (W[i - 15] >> 3);
+        let s1 = W[i - 2].rotate_right(17) ^ W[i - 2].rotate_right(19) ^ (W[i - 2] >> 10);
+        W[i] = s1
+            .wrapping_add(W[i - 7])
+            .wrapping_add(s0)
+            .wrapping_add(W[i - 16]);
+    }
+
+    let K = round_constants_u32();
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..64 {
+        let S1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let Ch = (e & f) ^ (!e & g);
+        let T1 = h
+            .wrapping_add(S1)
+            .wrapping_add(Ch)
+            .wrapping_add(K[i])
+            .wrapping_add(W[i]);
+        let S0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let Maj = (a & b) ^ (a & c) ^ (b & c);
+        let T2 = S0.wrapping_add(Maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(T1);
+        d = c;
+        c = b;
+        b = a;
+        a = T1.wrapping_add(T2);
+    }
+
+    for (word, working) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(working);
+    }
+}
+
+/// Tests the quick path against the field engine and `sha2`, around the padding boundaries.
+#[test]
+fn quick_test() {
+    use crate::{native_sha256::NativeSha256, sha_helpers::digest_to_bytes};
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::Digest;
+
+    for len in [0usize, 1, 3, 55, 56, 63, 64, 65, 119, 120, 200] {
+        let data: Vec<u8> = (0..len).map(|i| (i * 31) as u8).collect();
+        let quick = Sha256::quick(&data);
+        assert_eq!(
+            quick,
+            digest_to_bytes(NativeSha256::<Fp>::hash_bytes(&data)),
+            "Mismatch with the field path on {len} bytes."
+        );
+        assert_eq!(
+            quick,
+            <[u8; 32]>::from(sha2::Sha256::digest(&data)),
+            "Mismatch with sha2 on {len} bytes."
+        );
+    }
+}
@@ -2,14 +2,57 @@
 
 use ark_ff::PrimeField;
 
+use crate::{
+    digest::Digest,
+    errors::{PadError, Sha256Error},
+};
+
 // ========== Bit Conversion Utilities ========== //
 
 /// Converts a hex string to a vector of bits (big-endian).
 pub fn from_hex(hex: &str) -> Vec<u8> {
     let bytes = hex::decode(hex).expect("Invalid hex.");
+    bytes_to_bits(&bytes)
+}
+
+/// Order of the bits within each byte when expanding bytes to bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Most significant bit first, as the SHA256 specification requires.
+    #[default]
+    Msb0,
+    /// Least significant bit first. Non-standard: digests of `Lsb0` input will not match `sha2`.
+    /// Only meant for legacy systems that feed SHA256 with bit-reversed bytes.
+    Lsb0,
+}
+
+/// Converts a byte slice to a vector of bits (big-endian within each byte).
+pub fn bytes_to_bits(bytes: &[u8]) -> Vec<u8> {
+    bytes_to_bits_ordered(bytes, BitOrder::Msb0)
+}
+
+/// Converts a byte slice to a vector of bits, in the given order within each byte.
+pub fn bytes_to_bits_ordered(bytes: &[u8], order: BitOrder) -> Vec<u8> {
     bytes
         .iter()
-        .flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1))
+        .flat_map(|&byte| {
+            (0..8).map(move |i| match order {
+                BitOrder::Msb0 => (byte >> (7 - i)) & 1,
+                BitOrder::Lsb0 => (byte >> i) & 1,
+            })
+        })
+        .collect()
+}
+
+/// Packs bits (big-endian within each byte) into bytes; the inverse of `bytes_to_bits`.
+/// The bit count must be a multiple of 8.
+pub fn bits_to_bytes(bits: &[u8]) -> Vec<u8> {
+    assert!(
+        bits.len().is_multiple_of(8),
+        "Bit count must be a multiple of 8!"
+    );
+    bits.chunks_exact(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, &bit| (acc << 1) | bit))
         .collect()
 }
 
@@ -19,6 +62,12 @@ pub fn to_bits_be<T: Into<u64>, const N: usize>(num: T) -> [u8; N] {
     std::array::from_fn(|i| ((n >> (N - 1 - i)) & 1) as u8)
 }
 
+/// Converts a fixed-size big-endian bit array back into an integer; the inverse of `to_bits_be`.
+pub fn from_bits_be<const N: usize>(bits: &[u8; N]) -> u64 {
+    assert!(N <= 64, "Cannot decode more than 64 bits into a u64!");
+    bits.iter().fold(0u64, |acc, &bit| (acc << 1) | bit as u64)
+}
+
 /// Converts a bit slice into an array of field elements.
 pub fn bits_to_field<F: PrimeField, const N: usize>(bits: &[u8]) -> [F; N] {
     let mut arr = [F::zero(); N];
@@ -30,22 +79,65 @@ pub fn bits_to_field<F: PrimeField, const N: usize>(bits: &[u8]) -> [F; N] {
 
 // ========== Padding Utilities ========== //
 
+/// Returns the minimal padded length, in bits, of a message of `input_bits_len` bits:
+/// the message, the 1-bit marker and the 64-bit length field rounded up to whole blocks.
+pub fn min_padded_bits(input_bits_len: usize) -> usize {
+    (input_bits_len + 65).div_ceil(512) * 512
+}
+
+/// Returns the `digest_index` that [`sha256_pad`] reports for a message of `input_bits_len` bits:
+/// the position of the 64-bit length field after minimal padding. It does not depend on
+/// `max_bits`, since extra circuit blocks are appended after the length field.
+pub fn expected_digest_index(input_bits_len: usize) -> usize {
+    min_padded_bits(input_bits_len) - 64
+}
+
+/// Returns the number of zero-fill bits minimal padding inserts between the `1` marker and the
+/// 64-bit length field of a message of `input_bits_len` bits, i.e. `(447 - len) mod 512`: 0 for
+/// a 447-bit remainder, and the maximum of 511 when the length field no longer fits after the
+/// marker and the fill rolls over into a new block.
+pub fn padding_zero_count(input_bits_len: usize) -> usize {
+    expected_digest_index(input_bits_len) - input_bits_len - 1
+}
+
 /// Pads the bit-level SHA256 message to exactly `max_bits`, according to the SHA256 specification.
 /// This function performs bit-level padding including the 1-bit marker, 0-fill, and 64-bit length field.
 /// It ensures the message ends at a complete block boundary defined by `max_bits`.
-pub fn sha256_pad(input_bits: Vec<u8>, max_bits: usize) -> (Vec<u8>, usize) {
-    // Pad the input to match SHA256 requirements.
+///
+/// **`max_bits` beyond [`min_padded_bits`] changes the digest of a hasher that compresses every
+/// block**: the extra capacity is filled with whole all-zero blocks after the length field, and
+/// compressing them continues the chain. Use `max_bits = min_padded_bits(input_bits.len())` for a
+/// standard digest from `NativeSha256::hash`, or hash with `DynamicSha256::hash_message_blocks`,
+/// which uses the returned digest index to skip the capacity blocks.
+///
+/// Returns [`PadError::MaxBitsBelowOneBlock`] if `max_bits` is below 512,
+/// [`PadError::MaxBitsNotBlockAligned`] if `max_bits` is not a multiple of 512,
+/// [`PadError::MessageTooLong`] if the message has 2^64 bits or more (the limit of the length
+/// field), and [`PadError::InputTooLong`] if the padded message does not fit in `max_bits`.
+///
+/// With the `tracing` feature, input that already looks padded (see [`is_likely_padded`]) logs a
+/// warning: padding it again is valid but almost always a bug.
+pub fn sha256_pad(input_bits: Vec<u8>, max_bits: usize) -> Result<(Vec<u8>, usize), PadError> {
+    warn_if_padded(&input_bits);
+    let bit_length = input_bits.len();
+    if let Some(error) = pad_errors(bit_length as u128, max_bits).into_iter().next() {
+        return Err(error);
+    }
+
+    // Pad the input to match SHA256 requirements. The final length is known, so grow the
+    // buffer once up front instead of reallocating as the pushes below fill it.
     let mut padded = input_bits;
-    let bit_length = padded.len();
+    padded.reserve_exact(max_bits - padded.len());
     padded.push(1);
 
     while padded.len() % 512 != 448 {
         padded.push(0);
     }
+    // The checks passed, so the length fits the 64-bit length field.
     padded.extend_from_slice(&to_bits_be::<_, 64>(bit_length as u64));
 
     assert!(
-        padded.len() % 512 == 0,
+        padded.len().is_multiple_of(512),
         "Padding did not complete properly!"
     );
 
@@ -67,25 +159,244 @@ pub fn sha256_pad(input_bits: Vec<u8>, max_bits: usize) -> (Vec<u8>, usize) {
     // Index where the 64-bit message length field begins (i.e., right before the final 64 bits).
     let output_hash_index = pre_pad_len - 64;
 
-    (padded, output_hash_index)
+    Ok((padded, output_hash_index))
+}
+
+/// Checks that an externally built buffer is well-formed [`sha256_pad`] output, i.e. that
+/// [`NativeSha256::hash`](crate::native_sha256::NativeSha256::hash) can trust it.
+///
+/// The buffer must be a whole number of 512-bit blocks of 0/1 values, and some block must end in
+/// a 64-bit length `L` such that bit `L` is the `1` marker, the bits between the marker and the
+/// length field are zero, and the block is the last of the minimal padding of `L` bits. Any blocks
+/// after it (extra `max_bits` capacity) must be zero. Such a block is unique when it exists.
+pub fn validate_padding(padded: &[u8]) -> Result<(), PadError> {
+    padded_message_bits(padded).map(|_| ())
+}
+
+/// Returns the length, in bits, of the message inside a padded buffer, read from its length
+/// field. Performs the same checks as [`validate_padding`].
+pub fn padded_message_bits(padded: &[u8]) -> Result<usize, PadError> {
+    if !padded.len().is_multiple_of(512) {
+        return Err(PadError::BufferNotBlockAligned { len: padded.len() });
+    }
+    if let Some(index) = padded.iter().position(|bit| *bit > 1) {
+        return Err(PadError::NonBitValue { index });
+    }
+
+    for end in (512..=padded.len()).step_by(512) {
+        let length_bits: [u8; 64] = padded[end - 64..end].try_into().unwrap();
+        let len = from_bits_be(&length_bits);
+        // Lengths beyond the block are rejected before `min_padded_bits` could overflow.
+        let consistent = usize::try_from(len).ok().filter(|&len| {
+            len < end - 64
+                && min_padded_bits(len) == end
+                && padded[len] == 1
+                && padded[len + 1..end - 64].iter().all(|bit| *bit == 0)
+                && padded[end..].iter().all(|bit| *bit == 0)
+        });
+        if let Some(len) = consistent {
+            return Ok(len);
+        }
+    }
+
+    Err(PadError::InvalidPadding)
+}
+
+/// Heuristic for accidental double padding: returns `true` if `bits` already looks like
+/// [`sha256_pad`] output, i.e. it passes [`validate_padding`]. Ordinary block-aligned messages
+/// only match if some block happens to end in a length field consistent with its marker and
+/// zero fill, which is vanishingly unlikely for real data.
+#[must_use]
+pub fn is_likely_padded(bits: &[u8]) -> bool {
+    validate_padding(bits).is_ok()
+}
+
+/// Panics unless `padded` is a whole number of 512-bit blocks of 0/1 values.
+/// Values above 1 are checked first, since they usually mean raw bytes were passed where
+/// bit-expanded input was expected.
+pub(crate) fn assert_padded_bits(padded: &[u8]) {
+    assert!(
+        padded.iter().all(|bit| *bit <= 1),
+        "Preimage appears to contain bytes, not bits — did you forget bytes_to_bits?"
+    );
+    assert!(
+        padded.len().is_multiple_of(512),
+        "Input must be padded to 512-bit blocks."
+    );
+}
+
+/// Byte-level counterpart of [`sha256_pad`]: pads a byte message to exactly `max_bytes` packed
+/// bytes, so the padded preimage takes one byte per 8 bits instead of one byte per bit.
+/// Unpacking the result with [`bytes_to_bits`] gives `sha256_pad(bytes_to_bits(input), 8 * max_bytes)`,
+/// and the returned digest index is likewise in bits.
+///
+/// Returns [`PadError::TooManyBytes`] if `max_bytes` overflows `usize` when counted in bits, and
+/// otherwise the same errors as [`sha256_pad`].
+pub fn sha256_pad_bytes(input: Vec<u8>, max_bytes: usize) -> Result<(Vec<u8>, usize), PadError> {
+    let max_bits = max_bytes
+        .checked_mul(8)
+        .ok_or(PadError::TooManyBytes { max_bytes })?;
+    // Counted in `u128`: from 512 MiB on, the bit length overflows a 32-bit `usize`.
+    let bit_length = input.len() as u128 * 8;
+    if let Some(error) = pad_errors(bit_length, max_bits).into_iter().next() {
+        return Err(error);
+    }
+    // The checks passed, so the padded length is at most `max_bits` and fits in `usize`.
+    let required_bits = padded_bits(bit_length) as usize;
+
+    let mut padded = input;
+    padded.reserve_exact(max_bytes - padded.len());
+    padded.push(0x80);
+    padded.resize(required_bits / 8 - 8, 0);
+    padded.extend_from_slice(&(bit_length as u64).to_be_bytes());
+    padded.resize(max_bytes, 0);
+
+    Ok((padded, required_bits - 64))
+}
+
+/// Runs every check of [`sha256_pad`] on a message of `bit_length` bits padded to `max_bits`
+/// and returns all failures, in the order they are checked; empty when padding succeeds.
+/// The padding functions report the first failure, [`hash_checked`](crate::hashing::hash_checked)
+/// all of them. Takes a `u128` so lengths that overflow `usize` are checked instead of wrapped.
+pub fn pad_errors(bit_length: u128, max_bits: usize) -> Vec<PadError> {
+    let mut errors = Vec::new();
+    if max_bits < 512 {
+        errors.push(PadError::MaxBitsBelowOneBlock { got: max_bits });
+    }
+    if !max_bits.is_multiple_of(512) {
+        errors.push(PadError::MaxBitsNotBlockAligned { got: max_bits });
+    }
+    if let Err(error) = length_field(bit_length) {
+        errors.push(error);
+    }
+
+    // Reject inputs whose minimal padding (1-bit marker + 64-bit length) overflows `max_bits`.
+    let required_bits = padded_bits(bit_length);
+    if required_bits > max_bits as u128 {
+        errors.push(PadError::InputTooLong {
+            required_bits: usize::try_from(required_bits).unwrap_or(usize::MAX),
+            max_bits,
+        });
+    }
+    errors
+}
+
+/// Converts a message length in bits into SHA256's 64-bit length field, rejecting lengths of
+/// 2^64 bits or more. Takes a `u128` so the limit holds regardless of the width of `usize`.
+fn length_field(bit_length: u128) -> Result<u64, PadError> {
+    u64::try_from(bit_length).map_err(|_| PadError::MessageTooLong { bit_length })
+}
+
+/// [`min_padded_bits`] over a `u128` length, which cannot overflow for any in-memory message.
+pub(crate) fn padded_bits(bit_length: u128) -> u128 {
+    (bit_length + 65).div_ceil(512) * 512
+}
+
+/// Pads the bit-level SHA256 message to exactly `num_blocks` 512-bit blocks.
+/// Equivalent to `sha256_pad(input_bits, num_blocks * 512)`, except that a block count whose bit
+/// size overflows `usize` is rejected instead of wrapping.
+pub fn sha256_pad_blocks(
+    input_bits: Vec<u8>,
+    num_blocks: usize,
+) -> Result<(Vec<u8>, usize), PadError> {
+    let max_bits = num_blocks
+        .checked_mul(512)
+        .ok_or(PadError::TooManyBlocks { num_blocks })?;
+    sha256_pad(input_bits, max_bits)
 }
 
 // ========== Field Bitwise Logic ========== //
 
+/// Small field constants used by the bitwise helpers.
+/// Build it once with [`FieldConsts::new`] and pass it to the `*_with` variants in hot loops;
+/// the plain helpers build their own on every call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldConsts<F: PrimeField> {
+    pub zero: F,
+    pub one: F,
+    pub two: F,
+}
+
+impl<F: PrimeField> FieldConsts<F> {
+    /// Computes the constants.
+    pub fn new() -> Self {
+        Self {
+            zero: F::zero(),
+            one: F::one(),
+            two: F::from(2u8),
+        }
+    }
+}
+
+impl<F: PrimeField> Default for FieldConsts<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Word-level operation counters, bumped by the bitwise helpers in test builds so that cost
+/// estimates can be checked against an instrumented run.
+#[cfg(test)]
+pub(crate) mod op_counts {
+    use std::{cell::Cell, thread::LocalKey};
+
+    thread_local! {
+        pub(crate) static XOR: Cell<usize> = const { Cell::new(0) };
+        pub(crate) static AND: Cell<usize> = const { Cell::new(0) };
+        pub(crate) static NOT: Cell<usize> = const { Cell::new(0) };
+        pub(crate) static ADD: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub(crate) fn bump(counter: &'static LocalKey<Cell<usize>>) {
+        counter.with(|count| count.set(count.get() + 1));
+    }
+
+    /// Returns the `[xor, and, not, add]` counts of this thread and resets them.
+    pub(crate) fn take() -> [usize; 4] {
+        [&XOR, &AND, &NOT, &ADD].map(|counter| counter.with(|count| count.replace(0)))
+    }
+}
+
 /// Element-wise AND logic in the field.
 pub fn and<F: PrimeField, const N: usize>(a: [F; N], b: [F; N]) -> [F; N] {
+    #[cfg(test)]
+    op_counts::bump(&op_counts::AND);
     std::array::from_fn(|i| a[i] * b[i])
 }
 
 /// Element-wise NOT logic in the field.
 pub fn not<F: PrimeField, const N: usize>(a: [F; N]) -> [F; N] {
-    std::array::from_fn(|i| F::one() - a[i])
+    not_with(&FieldConsts::new(), a)
+}
+
+/// [`not`] with precomputed constants.
+pub fn not_with<F: PrimeField, const N: usize>(c: &FieldConsts<F>, a: [F; N]) -> [F; N] {
+    #[cfg(test)]
+    op_counts::bump(&op_counts::NOT);
+    std::array::from_fn(|i| c.one - a[i])
 }
 
 /// Performs bitwise XOR in the field.
 pub fn xor<F: PrimeField, const N: usize>(a: [F; N], b: [F; N]) -> [F; N] {
+    xor_with(&FieldConsts::new(), a, b)
+}
+
+/// [`xor`] with precomputed constants.
+pub fn xor_with<F: PrimeField, const N: usize>(c: &FieldConsts<F>, a: [F; N], b: [F; N]) -> [F; N] {
+    #[cfg(test)]
+    op_counts::bump(&op_counts::XOR);
     let and_ab = and(a, b);
-    std::array::from_fn(|i| a[i] + b[i] - F::from(2u8) * and_ab[i])
+    std::array::from_fn(|i| a[i] + b[i] - c.two * and_ab[i])
+}
+
+/// Element-wise selection in the field: returns `a` when `cond` is 1 and `b` when it is 0,
+/// computed as `cond * a + (1 - cond) * b`.
+pub fn select<F: PrimeField, const N: usize>(cond: F, a: [F; N], b: [F; N]) -> [F; N] {
+    debug_assert!(
+        cond == F::zero() || cond == F::one(),
+        "Selection condition must be boolean!"
+    );
+    std::array::from_fn(|i| cond * a[i] + (F::one() - cond) * b[i])
 }
 
 /// Bitwise rotate-right.
@@ -108,28 +419,60 @@ pub fn right_shift<F: PrimeField, const N: usize>(shift: usize, word: [F; N]) ->
 
 /// Modular addition in binary form (mod 2^32).
 pub fn wrapping_add<F: PrimeField>(a: [F; 32], b: [F; 32]) -> [F; 32] {
-    let mut result = [F::zero(); 32];
-    let mut carry = F::zero();
-    let one = F::one();
-    let two = one + one;
+    wrapping_add_with(&FieldConsts::new(), a, b)
+}
+
+/// [`wrapping_add`] with precomputed constants.
+pub fn wrapping_add_with<F: PrimeField>(c: &FieldConsts<F>, a: [F; 32], b: [F; 32]) -> [F; 32] {
+    ripple_add(c, a, b)
+}
 
-    for i in (0..32).rev() {
+/// Ripple-carry addition of two `N`-bit big-endian words, mod 2^N.
+pub(crate) fn ripple_add<F: PrimeField, const N: usize>(
+    c: &FieldConsts<F>,
+    a: [F; N],
+    b: [F; N],
+) -> [F; N] {
+    #[cfg(test)]
+    op_counts::bump(&op_counts::ADD);
+    let mut result = [c.zero; N];
+    let mut carry = c.zero;
+
+    for i in (0..N).rev() {
         let sum = a[i] + b[i] + carry;
-        if sum >= two {
-            result[i] = sum - two;
-            carry = one;
+        if sum >= c.two {
+            result[i] = sum - c.two;
+            carry = c.one;
         } else {
             result[i] = sum;
-            carry = F::zero();
+            carry = c.zero;
         }
     }
 
     result
 }
 
+/// [`wrapping_add`] through native integer arithmetic: both words are decoded to `u32`s, added,
+/// and encoded back to bits. The result is identical for boolean inputs and it is about 3x faster
+/// natively (`cargo bench --bench wrapping_add`), but it has no gate-level counterpart. The
+/// compression therefore keeps the ripple-carry [`wrapping_add`], which mirrors the 32-step carry
+/// chain a circuit would lay out.
+pub fn wrapping_add_fast<F: PrimeField>(a: [F; 32], b: [F; 32]) -> [F; 32] {
+    let sum = bits_to_u32(a).wrapping_add(bits_to_u32(b));
+    let (zero, one) = (F::zero(), F::one());
+    std::array::from_fn(|i| {
+        if (sum >> (31 - i)) & 1 == 1 {
+            one
+        } else {
+            zero
+        }
+    })
+}
+
 // ========== Digest Utilities ========== //
 
 /// Converts a 32-bit array of field elements to a `u32`, interpreting bits as big-endian.
+#[must_use]
 pub fn bits_to_u32<F: PrimeField>(bits: [F; 32]) -> u32 {
     bits.iter().enumerate().fold(0u32, |acc, (i, bit)| {
         let b = if *bit == F::zero() { 0 } else { 1 };
@@ -138,9 +481,875 @@ pub fn bits_to_u32<F: PrimeField>(bits: [F; 32]) -> u32 {
 }
 
 /// Converts final state words into a hex digest.
-pub fn digest_to_hex<F: PrimeField>(H: [[F; 32]; 8]) -> String {
+#[must_use]
+pub fn digest_to_hex<F: PrimeField>(H: Digest<F>) -> String {
     H.iter()
         .map(|word| format!("{:08x}", bits_to_u32(*word)))
         .collect::<Vec<_>>()
         .join("")
 }
+
+/// Converts final state words into an uppercase hex digest, e.g. for certificate thumbprints.
+#[must_use]
+pub fn digest_to_hex_upper<F: PrimeField>(H: Digest<F>) -> String {
+    H.iter()
+        .map(|word| format!("{:08X}", bits_to_u32(*word)))
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+/// Converts final state words into the 32-byte big-endian digest.
+#[must_use]
+pub fn digest_to_bytes<F: PrimeField>(H: Digest<F>) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(H.iter()) {
+        chunk.copy_from_slice(&bits_to_u32(*word).to_be_bytes());
+    }
+    bytes
+}
+
+/// Converts final state words into a 256-bit unsigned integer, reading the digest big-endian.
+/// Unlike field packings, the value is not reduced modulo the field order.
+#[cfg(feature = "biguint")]
+#[must_use]
+pub fn digest_to_biguint<F: PrimeField>(H: Digest<F>) -> num_bigint::BigUint {
+    num_bigint::BigUint::from_bytes_be(&digest_to_bytes(H))
+}
+
+/// Converts final state words into a standard RFC 4648 base64 digest, with padding.
+#[cfg(feature = "base64")]
+#[must_use]
+pub fn digest_to_base64<F: PrimeField>(H: Digest<F>) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD.encode(digest_to_bytes(H))
+}
+
+/// Converts final state words into an RFC 4648 base64url digest without padding, as used in
+/// JWTs and URLs.
+#[cfg(feature = "base64")]
+#[must_use]
+pub fn digest_to_base64url<F: PrimeField>(H: Digest<F>) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest_to_bytes(H))
+}
+
+/// Maps a digest to a field element for Fiat-Shamir challenges: the 256 digest bits are read as a
+/// big-endian integer and reduced modulo the field order `p`.
+///
+/// Reduction favors the residues below `2^256 mod p`, at a statistical distance from uniform of
+/// less than `p / 2^256`. For the Pasta fields `2^256` is just under `4p` and the distance is
+/// about `2^-129`, which is negligible; for a field whose order is far from a divisor of `2^256`,
+/// use wide reduction instead: derive 512 bits (e.g. two domain-separated digests, as in
+/// `expand_message_xmd` of RFC 9380) and reduce those, which pushes the bias below `2^-256`.
+#[must_use]
+pub fn digest_to_field_uniform<F: PrimeField>(H: Digest<F>) -> F {
+    F::from_be_bytes_mod_order(&digest_to_bytes(H))
+}
+
+/// Returns the leading `N` bytes of the big-endian digest (`N <= 32`).
+#[must_use]
+pub fn truncate_digest<F: PrimeField, const N: usize>(H: Digest<F>) -> [u8; N] {
+    assert!(N <= 32, "Cannot truncate a digest to more than 32 bytes!");
+    let bytes = digest_to_bytes(H);
+    std::array::from_fn(|i| bytes[i])
+}
+
+/// Splits the big-endian digest into its high and low 128-bit halves, each big-endian.
+#[must_use]
+pub fn digest_halves<F: PrimeField>(H: Digest<F>) -> ([u8; 16], [u8; 16]) {
+    let bytes = digest_to_bytes(H);
+    let (high, low) = bytes.split_at(16);
+    (high.try_into().unwrap(), low.try_into().unwrap())
+}
+
+/// Checks that every bit of a table of words (e.g. a custom initial state or custom round
+/// constants) is 0 or 1.
+pub fn validate_state<F: PrimeField, const N: usize>(
+    state: &[[F; 32]; N],
+) -> Result<(), Sha256Error> {
+    for (word, bits) in state.iter().enumerate() {
+        if let Some(bit) = bits.iter().position(|b| *b != F::zero() && *b != F::one()) {
+            return Err(Sha256Error::NonBooleanState { word, bit });
+        }
+    }
+    Ok(())
+}
+
+/// Compares two byte slices in constant time with respect to their contents.
+/// Slices of different lengths compare unequal.
+#[must_use]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Converts a 32-byte big-endian digest back into state words; the inverse of `digest_to_bytes`.
+#[must_use]
+pub fn bytes_to_digest<F: PrimeField>(bytes: &[u8; 32]) -> Digest<F> {
+    let bits = bytes_to_bits(bytes);
+    std::array::from_fn(|i| bits_to_field(&bits[32 * i..32 * (i + 1)]))
+}
+
+// ========== Tracing Utilities ========== //
+
+/// Enters a span for the compression of block `block`; a no-op without the `tracing` feature.
+#[cfg(feature = "tracing")]
+pub(crate) fn block_span(block: usize) -> tracing::span::EnteredSpan {
+    tracing::trace_span!("process_chunk", block).entered()
+}
+
+/// Placeholder span guard used when the `tracing` feature is disabled.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
+
+/// Enters a span for the compression of block `block`; a no-op without the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn block_span(_block: usize) -> NoSpan {
+    NoSpan
+}
+
+/// Emits the final digest as a hex event; a no-op without the `tracing` feature.
+pub(crate) fn trace_digest<F: PrimeField>(_H: Digest<F>) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(digest = %digest_to_hex(_H), "sha256 digest");
+}
+
+/// Warns when [`sha256_pad`] is handed input that already looks padded; a no-op without the
+/// `tracing` feature, so the check costs nothing then.
+fn warn_if_padded(_input_bits: &[u8]) {
+    #[cfg(feature = "tracing")]
+    if is_likely_padded(_input_bits) {
+        tracing::warn!(
+            bits = _input_bits.len(),
+            "sha256_pad input already looks padded; padding it again hashes the padding too"
+        );
+    }
+}
+
+/// Tests `sha256_pad` layout and error reporting.
+#[test]
+fn sha256_pad_test() {
+    // === Test 1: Input needing three blocks does not fit in two ===
+    let bits = vec![1u8; 1000];
+    assert_eq!(
+        sha256_pad(bits.clone(), 1024),
+        Err(PadError::InputTooLong {
+            required_bits: 1536,
+            max_bits: 1024
+        }),
+        "Expected an input too long error."
+    );
+
+    // === Test 2: The same input fits in three blocks ===
+    let (padded, digest_index) = sha256_pad(bits, 1536).unwrap();
+    assert_eq!(padded.len(), 1536, "Mismatch on padded length.");
+    assert_eq!(digest_index, 1472, "Mismatch on digest index.");
+
+    // === Test 3: max_bits must be block aligned ===
+    assert_eq!(
+        sha256_pad(from_hex("00"), 1000),
+        Err(PadError::MaxBitsNotBlockAligned { got: 1000 }),
+        "Expected a block alignment error."
+    );
+    assert!(
+        sha256_pad(from_hex("00"), 1024).is_ok(),
+        "Aligned max_bits rejected."
+    );
+
+    // === Test 4: Block-count padding matches bit-count padding ===
+    let bits = from_hex("deadbeef");
+    assert_eq!(
+        sha256_pad_blocks(bits.clone(), 2),
+        sha256_pad(bits, 1024),
+        "Mismatch between block and bit padding."
+    );
+    assert_eq!(
+        sha256_pad_blocks(vec![0u8; 1000], 2),
+        Err(PadError::InputTooLong {
+            required_bits: 1536,
+            max_bits: 1024
+        }),
+        "Expected an input too long error."
+    );
+    // One block past the limit, whose unchecked size would wrap to 512 bits.
+    let num_blocks = usize::MAX / 512 + 2;
+    assert_eq!(
+        sha256_pad_blocks(vec![1u8; 8], num_blocks),
+        Err(PadError::TooManyBlocks { num_blocks }),
+        "Overflowing block count accepted."
+    );
+
+    // === Test 5: Packed padding unpacks to bit padding ===
+    for len in [0, 3, 55, 56, 64, 119] {
+        let bytes: Vec<u8> = (0..len as u8).collect();
+        let (packed, packed_index) = sha256_pad_bytes(bytes.clone(), 192).unwrap();
+        let (bits, index) = sha256_pad(bytes_to_bits(&bytes), 1536).unwrap();
+        assert_eq!(bytes_to_bits(&packed), bits, "Mismatch on {len} bytes.");
+        assert_eq!(packed_index, index, "Index mismatch on {len} bytes.");
+    }
+    assert_eq!(
+        sha256_pad_bytes(vec![0u8; 56], 64),
+        Err(PadError::InputTooLong {
+            required_bits: 1024,
+            max_bits: 512
+        }),
+        "Expected an input too long error."
+    );
+
+    // === Test 6: Digest index is computed, not hardcoded ===
+    // Two and four Pasta field elements, as in the engine tests.
+    assert_eq!(expected_digest_index(512), 960, "Mismatch on 512 bits.");
+    assert_eq!(expected_digest_index(1024), 1472, "Mismatch on 1024 bits.");
+    for len in [0, 447, 448, 511, 512, 1000] {
+        let (_, index) = sha256_pad(vec![0u8; len], 2048).unwrap();
+        assert_eq!(expected_digest_index(len), index, "Mismatch on {len} bits.");
+    }
+
+    // === Test 7: Padding validation ===
+    let (padded, _) = sha256_pad(from_hex("deadbeef"), 512).unwrap();
+    assert_eq!(
+        validate_padding(&padded),
+        Ok(()),
+        "Minimal padding rejected."
+    );
+    let (extended, _) = sha256_pad(vec![1u8; 500], 2048).unwrap();
+    assert_eq!(
+        validate_padding(&extended),
+        Ok(()),
+        "Extended padding rejected."
+    );
+
+    let mut no_marker = padded.clone();
+    no_marker[32] = 0;
+    assert_eq!(
+        validate_padding(&no_marker),
+        Err(PadError::InvalidPadding),
+        "Missing marker accepted."
+    );
+    let mut bad_length = padded.clone();
+    bad_length[511] = 1;
+    assert_eq!(
+        validate_padding(&bad_length),
+        Err(PadError::InvalidPadding),
+        "Wrong length field accepted."
+    );
+    let mut huge_length = padded.clone();
+    huge_length[448..].fill(1);
+    assert_eq!(
+        validate_padding(&huge_length),
+        Err(PadError::InvalidPadding),
+        "Length beyond the buffer accepted."
+    );
+    let mut non_bit = padded.clone();
+    non_bit[7] = 0xff;
+    assert_eq!(
+        validate_padding(&non_bit),
+        Err(PadError::NonBitValue { index: 7 }),
+        "Non-bit value accepted."
+    );
+    assert_eq!(
+        validate_padding(&padded[..448]),
+        Err(PadError::BufferNotBlockAligned { len: 448 }),
+        "Partial block accepted."
+    );
+
+    // === Test 8: Length field boundary, on synthetic lengths ===
+    assert_eq!(length_field(0), Ok(0), "Mismatch on empty message.");
+    assert_eq!(
+        length_field(u64::MAX as u128),
+        Ok(u64::MAX),
+        "Largest length must fit."
+    );
+    assert_eq!(
+        length_field(1 << 64),
+        Err(PadError::MessageTooLong {
+            bit_length: 1 << 64
+        }),
+        "Expected a message too long error."
+    );
+    // A 512 MiB byte message, whose bit length overflows a 32-bit `usize`.
+    let bit_length = (512u128 << 20) * 8;
+    assert_eq!(
+        length_field(bit_length),
+        Ok(1 << 32),
+        "Mismatch on a length beyond 32 bits."
+    );
+    assert_eq!(
+        padded_bits(bit_length),
+        (1 << 32) + 512,
+        "Mismatch on padded length beyond 32 bits."
+    );
+    assert_eq!(
+        sha256_pad_bytes(vec![], usize::MAX),
+        Err(PadError::TooManyBytes {
+            max_bytes: usize::MAX
+        }),
+        "Expected a too many bytes error."
+    );
+
+    // === Test 9: Capacity below one block, even for the empty message ===
+    for max_bits in [0, 256] {
+        let err = sha256_pad(vec![], max_bits).unwrap_err();
+        assert_eq!(
+            err,
+            PadError::MaxBitsBelowOneBlock { got: max_bits },
+            "Expected a below-one-block error for {max_bits} bits."
+        );
+        assert!(
+            err.to_string().contains("at least 512"),
+            "Error must explain the 512-bit minimum."
+        );
+    }
+    assert_eq!(
+        sha256_pad_bytes(vec![], 32),
+        Err(PadError::MaxBitsBelowOneBlock { got: 256 }),
+        "Expected a below-one-block error on packed padding."
+    );
+
+    // === Test 10: Every failed check is collected, on a synthetic length ===
+    assert!(pad_errors(1000, 1536).is_empty(), "Valid padding rejected.");
+    assert_eq!(
+        pad_errors(1 << 64, 1000),
+        vec![
+            PadError::MaxBitsNotBlockAligned { got: 1000 },
+            PadError::MessageTooLong {
+                bit_length: 1 << 64
+            },
+            PadError::InputTooLong {
+                required_bits: usize::MAX,
+                max_bits: 1000
+            },
+        ],
+        "Mismatch on collected errors."
+    );
+}
+
+/// Tests that `from_bits_be` inverts `to_bits_be`.
+#[test]
+fn bits_be_round_trip_test() {
+    for x in [0u64, 1, 0x80, 0xdead_beef, u64::MAX] {
+        assert_eq!(from_bits_be(&to_bits_be::<_, 64>(x)), x, "Mismatch on {x}.");
+    }
+    for x in [0u32, 1, 0x6a09e667, u32::MAX] {
+        assert_eq!(
+            from_bits_be(&to_bits_be::<_, 32>(x)),
+            x as u64,
+            "Mismatch on {x}."
+        );
+    }
+    assert_eq!(
+        from_bits_be(&to_bits_be::<_, 8>(0xa5u8)),
+        0xa5,
+        "Mismatch on 0xa5."
+    );
+}
+
+/// Tests `select` picks the expected word for both condition values.
+#[test]
+fn select_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let a: [Fp; 32] = bits_to_field(&to_bits_be::<_, 32>(0xdeadbeefu32));
+    let b: [Fp; 32] = bits_to_field(&to_bits_be::<_, 32>(0x01234567u32));
+
+    assert_eq!(select(Fp::from(1u8), a, b), a, "Mismatch on cond = 1.");
+    assert_eq!(select(Fp::from(0u8), a, b), b, "Mismatch on cond = 0.");
+}
+
+/// Tests that `from_hex` keeps leading zero bytes and hashes them like `sha2`.
+#[test]
+fn from_hex_leading_zeros_test() {
+    use crate::native_sha256::NativeSha256;
+    use kimchi::mina_curves::pasta::Fp;
+    use sha2::{Digest, Sha256};
+
+    assert_eq!(
+        from_hex("0000ff").len(),
+        24,
+        "Mismatch on bit length of 0x0000ff."
+    );
+    assert_eq!(
+        from_hex("0000ff")[..16],
+        [0u8; 16],
+        "Leading zeros were dropped."
+    );
+
+    for hex in ["00", "0000", "00ff"] {
+        let bits = from_hex(hex);
+        assert_eq!(
+            bits.len(),
+            hex.len() * 4,
+            "Mismatch on bit length of 0x{hex}."
+        );
+
+        let max_bits = min_padded_bits(bits.len());
+        let (padded, _) = sha256_pad(bits, max_bits).unwrap();
+        let hash = NativeSha256::<Fp>::new(padded).hash();
+        let std_hash = Sha256::digest(hex::decode(hex).unwrap());
+
+        assert_eq!(
+            digest_to_hex(hash),
+            hex::encode(std_hash),
+            "Mismatch on 0x{hex}."
+        );
+    }
+}
+
+/// Tests `wrapping_add` against `u32::wrapping_add` on inputs chosen for long carry ripples.
+#[test]
+fn wrapping_add_structured_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let mut values: Vec<u32> = (0..32).map(|i| 1 << i).collect();
+    values.extend((0..32).map(|i| u32::MAX >> i));
+    values.extend([
+        0,
+        u32::MAX,
+        0xaaaa_aaaa,
+        0x5555_5555,
+        0x7fff_ffff,
+        0x8000_0001,
+    ]);
+
+    let word = |x: u32| bits_to_field::<Fp, 32>(&to_bits_be::<_, 32>(x));
+    for &a in &values {
+        for &b in &values {
+            assert_eq!(
+                bits_to_u32(wrapping_add(word(a), word(b))),
+                a.wrapping_add(b),
+                "Mismatch on {a:#010x} + {b:#010x}."
+            );
+            assert_eq!(
+                wrapping_add_fast(word(a), word(b)),
+                wrapping_add(word(a), word(b)),
+                "Fast adder diverges on {a:#010x} + {b:#010x}."
+            );
+        }
+    }
+
+    // The carry out of bit 0 ripples through all 32 bits and is dropped.
+    assert_eq!(
+        bits_to_u32(wrapping_add(word(u32::MAX), word(1))),
+        0,
+        "Mismatch on the full carry chain."
+    );
+}
+
+/// Tests the adder's worst case: all-ones operands, alone and chained as in the compression.
+#[test]
+fn wrapping_add_all_ones_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let word = |x: u32| bits_to_field::<Fp, 32>(&to_bits_be::<_, 32>(x));
+    let ones = word(u32::MAX);
+
+    // Every bit position generates a carry; the top one is discarded.
+    assert_eq!(
+        bits_to_u32(wrapping_add(ones, ones)),
+        0xffff_fffe,
+        "Mismatch on 0xffffffff + 0xffffffff."
+    );
+
+    // Chains of three additions, like `T1 = h + Σ1 + Ch + K + W`.
+    for x in [u32::MAX, 0xffff_fffe, 1, 0x8000_0000] {
+        let chained = wrapping_add(wrapping_add(wrapping_add(ones, ones), ones), word(x));
+        assert_eq!(
+            bits_to_u32(chained),
+            u32::MAX
+                .wrapping_add(u32::MAX)
+                .wrapping_add(u32::MAX)
+                .wrapping_add(x),
+            "Mismatch on the chain ending in {x:#010x}."
+        );
+    }
+    assert_eq!(
+        bits_to_u32(wrapping_add(wrapping_add(ones, ones), ones)),
+        0xffff_fffd,
+        "Mismatch on three all-ones words."
+    );
+}
+
+/// Tests that the `*_with` helpers agree with the plain ones.
+#[test]
+fn field_consts_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let consts = FieldConsts::<Fp>::new();
+    assert_eq!(consts.two, consts.one + consts.one, "Mismatch on two.");
+
+    let a = bits_to_field::<Fp, 32>(&to_bits_be::<_, 32>(0xdead_beefu32));
+    let b = bits_to_field::<Fp, 32>(&to_bits_be::<_, 32>(0x0123_4567u32));
+    assert_eq!(xor_with(&consts, a, b), xor(a, b), "Mismatch on xor.");
+    assert_eq!(not_with(&consts, a), not(a), "Mismatch on not.");
+    assert_eq!(
+        wrapping_add_with(&consts, a, b),
+        wrapping_add(a, b),
+        "Mismatch on wrapping_add."
+    );
+}
+
+/// Tests the uppercase hex encoding against a known digest.
+#[test]
+fn digest_to_hex_upper_test() {
+    use crate::native_sha256::NativeSha256;
+    use kimchi::mina_curves::pasta::Fp;
+
+    let digest = NativeSha256::<Fp>::hash_bytes(b"abc");
+    assert_eq!(
+        digest_to_hex_upper(digest),
+        "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD",
+        "Mismatch on uppercase hex."
+    );
+    assert_eq!(
+        digest_to_hex_upper(digest),
+        digest_to_hex(digest).to_uppercase(),
+        "Mismatch with the uppercased lowercase hex."
+    );
+}
+
+/// Tests that `digest_halves` splits the digest bytes exactly at 128 bits.
+#[test]
+fn digest_halves_test() {
+    use kimchi::mina_curves::pasta::Fp;
+
+    let bytes: [u8; 32] = std::array::from_fn(|i| i as u8);
+    let digest = bytes_to_digest::<Fp>(&bytes);
+    let (high, low) = digest_halves(digest);
+
+    assert_eq!(
+        [high, low].concat(),
+        digest_to_bytes(digest),
+        "Halves must concatenate to the digest."
+    );
+    assert_eq!(high[15], 15, "High half must end at byte 15.");
+    assert_eq!(low[0], 16, "Low half must start at byte 16.");
+    assert_eq!(
+        u128::from_be_bytes(high),
+        u128::from_be_bytes(truncate_digest::<Fp, 16>(digest)),
+        "High half must be the leading 128 bits."
+    );
+}
+
+/// Tests the base64 formatters against known encodings, whose `+`/`/` and `-`/`_` positions
+/// exercise both alphabets.
+#[cfg(feature = "base64")]
+#[test]
+fn digest_to_base64_test() {
+    use crate::native_sha256::NativeSha256;
+    use kimchi::mina_curves::pasta::Fp;
+
+    let abc = NativeSha256::<Fp>::hash_bytes(b"abc");
+    assert_eq!(
+        digest_to_base64(abc),
+        "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=",
+        "Mismatch on base64."
+    );
+    assert_eq!(
+        digest_to_base64url(abc),
+        "ungWv48Bz-pBQUDeXa4iI7ADYaOWF3qctBD_YfIAFa0",
+        "Mismatch on base64url."
+    );
+
+    let empty = NativeSha256::<Fp>::hash_bytes(b"");
+    assert_eq!(
+        digest_to_base64(empty),
+        "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=",
+        "Mismatch on base64 of the empty message."
+    );
+    assert_eq!(
+        digest_to_base64url(empty),
+        "47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU",
+        "Mismatch on base64url of the empty message."
+    );
+}
+
+/// Tests the reduction of digests into field elements.
+#[test]
+fn digest_to_field_uniform_test() {
+    use crate::native_sha256::NativeSha256;
+    use ark_ff::Field;
+    use kimchi::mina_curves::pasta::Fp;
+
+    let mut small = [0u8; 32];
+    small[31] = 5;
+    assert_eq!(
+        digest_to_field_uniform(bytes_to_digest::<Fp>(&small)),
+        Fp::from(5u8),
+        "Small digests must map to themselves."
+    );
+    assert_eq!(
+        digest_to_field_uniform(bytes_to_digest::<Fp>(&[0xff; 32])),
+        Fp::from(2u8).pow([256]) - Fp::from(1u8),
+        "All-ones digest must map to 2^256 - 1 mod p."
+    );
+
+    // Deterministic, and distinct digests give distinct elements.
+    let digests: Vec<_> = (0..32u8)
+        .map(|i| NativeSha256::<Fp>::hash_bytes(&[i]))
+        .collect();
+    let elements: Vec<Fp> = digests
+        .iter()
+        .map(|d| digest_to_field_uniform(*d))
+        .collect();
+    for (i, digest) in digests.iter().enumerate() {
+        assert_eq!(
+            digest_to_field_uniform(*digest),
+            elements[i],
+            "Reduction must be deterministic."
+        );
+        assert!(
+            !elements[i + 1..].contains(&elements[i]),
+            "Distinct digests collided in the field."
+        );
+    }
+}
+
+/// Tests that `digest_to_biguint` reads the digest bytes big-endian.
+#[cfg(feature = "biguint")]
+#[test]
+fn digest_to_biguint_test() {
+    use crate::native_sha256::NativeSha256;
+    use kimchi::mina_curves::pasta::Fp;
+    use num_bigint::BigUint;
+
+    let digest = NativeSha256::<Fp>::hash_bytes(b"abc");
+    let value = digest_to_biguint(digest);
+
+    assert_eq!(
+        value,
+        BigUint::from_bytes_be(&digest_to_bytes(digest)),
+        "Mismatch with big-endian bytes."
+    );
+    assert_eq!(
+        value.to_str_radix(16),
+        digest_to_hex(digest).trim_start_matches('0'),
+        "Mismatch with hex digest."
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    /// Tests that rotations compose additively modulo the word size and that a full rotation is
+    /// the identity.
+    #[test]
+    fn rotate_right_compose_test(x: u32, a in 0usize..64, b in 0usize..64) {
+        use kimchi::mina_curves::pasta::Fp;
+
+        let word = bits_to_field::<Fp, 32>(&to_bits_be::<_, 32>(x));
+        proptest::prop_assert_eq!(
+            rotate_right(a, rotate_right(b, word)),
+            rotate_right((a + b) % 32, word),
+            "Rotations by {} and {} do not compose.",
+            a,
+            b
+        );
+        proptest::prop_assert_eq!(
+            rotate_right(32, word),
+            word,
+            "Full rotation is not the identity."
+        );
+        proptest::prop_assert_eq!(
+            bits_to_u32(rotate_right(a, word)),
+            x.rotate_right(a as u32),
+            "Mismatch with u32::rotate_right by {}.",
+            a
+        );
+    }
+
+    /// Tests the Boolean identities of the field logic on random words.
+    #[test]
+    fn boolean_identities_test(x: u32, y: u32) {
+        use kimchi::mina_curves::pasta::Fp;
+
+        let word = |v: u32| bits_to_field::<Fp, 32>(&to_bits_be::<_, 32>(v));
+        let (x, y, zero) = (word(x), word(y), word(0));
+
+        proptest::prop_assert_eq!(not(not(x)), x, "NOT is not an involution.");
+        proptest::prop_assert_eq!(xor(x, x), zero, "x XOR x is not zero.");
+        proptest::prop_assert_eq!(xor(x, zero), x, "Zero is not the XOR identity.");
+        proptest::prop_assert_eq!(and(x, x), x, "AND is not idempotent.");
+        proptest::prop_assert_eq!(xor(x, y), xor(y, x), "XOR does not commute.");
+        proptest::prop_assert_eq!(and(x, not(x)), zero, "x AND NOT x is not zero.");
+        proptest::prop_assert_eq!(not(x), xor(x, word(u32::MAX)), "NOT differs from XOR with all ones.");
+    }
+
+    /// Tests that `rotate_right` permutes the bit positions, losing or duplicating none.
+    #[test]
+    fn rotate_right_permutation_test(rot in 0usize..64) {
+        use kimchi::mina_curves::pasta::Fp;
+
+        // Tag every position with a distinct value so each can be tracked through the rotation.
+        let word: [Fp; 32] = std::array::from_fn(|i| Fp::from(i as u64));
+        let rotated = rotate_right(rot, word);
+
+        for (i, value) in word.iter().enumerate() {
+            proptest::prop_assert_eq!(
+                rotated.iter().filter(|v| *v == value).count(),
+                1,
+                "Position {} lost or duplicated.",
+                i
+            );
+            proptest::prop_assert_eq!(
+                rotated[(i + rot) % 32],
+                *value,
+                "Position {} not moved by {}.",
+                i,
+                rot
+            );
+        }
+    }
+
+    /// Tests the hex output contract: exactly 64 lowercase hex digits, every word zero-padded to
+    /// 8 digits, for any digest.
+    #[test]
+    fn digest_to_hex_format_test(bytes: [u8; 32], zero_word in 0usize..8) {
+        use kimchi::mina_curves::pasta::Fp;
+
+        // Force a word with leading zero bytes, the case a missing `{:08x}` width would break.
+        let mut bytes = bytes;
+        bytes[4 * zero_word..4 * zero_word + 2].fill(0);
+        let hex = digest_to_hex(bytes_to_digest::<Fp>(&bytes));
+
+        proptest::prop_assert_eq!(hex.len(), 64, "Hex digest must have 64 digits.");
+        proptest::prop_assert!(
+            hex.bytes().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')),
+            "Hex digest must be lowercase hex."
+        );
+        proptest::prop_assert_eq!(hex, hex::encode(bytes), "Mismatch with hex::encode.");
+    }
+}
+
+/// Tests zero-padding of a real digest with a leading-zero word: SHA256("105") has word 2 equal
+/// to `0x00266caa`.
+#[test]
+fn digest_to_hex_leading_zero_word_test() {
+    use crate::native_sha256::NativeSha256;
+    use kimchi::mina_curves::pasta::Fp;
+
+    let digest = NativeSha256::<Fp>::hash_bytes(b"105");
+    assert_eq!(bits_to_u32(digest[2]), 0x0026_6caa, "Unexpected word 2.");
+    assert_eq!(
+        digest_to_hex(digest),
+        "1253e9373e781b7500266caa55150e08e210bc8cd8cc70d89985e3600155e860",
+        "Leading zeros of word 2 dropped."
+    );
+}
+
+/// Tests that re-padding `sha256_pad` output is flagged, but ordinary aligned messages are not.
+#[test]
+fn is_likely_padded_test() {
+    for len in [0, 55, 56, 64, 200] {
+        let bits = bytes_to_bits(&vec![0x61; len]);
+        let max_bits = min_padded_bits(bits.len());
+        let (padded, _) = sha256_pad(bits.clone(), max_bits).unwrap();
+        assert!(is_likely_padded(&padded), "Padded {len} bytes not flagged.");
+        let (extended, _) = sha256_pad(bits, max_bits + 1024).unwrap();
+        assert!(
+            is_likely_padded(&extended),
+            "Padded {len} bytes with capacity not flagged."
+        );
+    }
+
+    // Block-aligned messages that are not padding.
+    for message in [
+        vec![0u8; 64],
+        vec![0xffu8; 64],
+        vec![0x61u8; 128],
+        (0..=255u8).collect::<Vec<u8>>(),
+    ] {
+        assert!(
+            !is_likely_padded(&bytes_to_bits(&message)),
+            "False positive on a {}-byte message.",
+            message.len()
+        );
+    }
+    assert!(
+        !is_likely_padded(&bytes_to_bits(&[0x61; 10])),
+        "False positive on an unaligned message."
+    );
+}
+
+/// Tests that `sha256_pad` warns exactly once when handed its own output, and not otherwise.
+#[cfg(feature = "tracing")]
+#[test]
+fn double_padding_warning_test() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tracing::{span, Event, Level, Metadata, Subscriber};
+
+    #[derive(Clone, Default)]
+    struct Counter {
+        warnings: Arc<AtomicUsize>,
+    }
+
+    impl Subscriber for Counter {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, event: &Event<'_>) {
+            if *event.metadata().level() == Level::WARN {
+                self.warnings.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let counter = Counter::default();
+    tracing::subscriber::with_default(counter.clone(), || {
+        let (padded, _) = sha256_pad(bytes_to_bits(b"abc"), 512).unwrap();
+        assert_eq!(
+            counter.warnings.load(Ordering::SeqCst),
+            0,
+            "Unpadded input must not warn."
+        );
+        let _ = sha256_pad(padded, 1024).unwrap();
+    });
+
+    assert_eq!(
+        counter.warnings.load(Ordering::SeqCst),
+        1,
+        "Expected one warning for padding already padded input."
+    );
+}
+
+/// Tests the zero-fill count against the layout of `sha256_pad` output.
+#[test]
+fn padding_zero_count_test() {
+    // (message bits, zero-fill bits): no fill, maximal fill on rollover, and in between.
+    for (len, zeros) in [
+        (447, 0),
+        (448, 511),
+        (959, 0),
+        (960, 511),
+        (0, 447),
+        (446, 1),
+        (512, 447),
+    ] {
+        assert_eq!(padding_zero_count(len), zeros, "Mismatch on {len} bits.");
+    }
+
+    for len in [0, 1, 440, 447, 448, 449, 511, 512, 1000] {
+        let (padded, digest_index) = sha256_pad(vec![1; len], min_padded_bits(len)).unwrap();
+        assert_eq!(padded[len], 1, "Missing marker after {len} bits.");
+        let fill = &padded[len + 1..digest_index];
+        assert!(
+            fill.iter().all(|bit| *bit == 0),
+            "Nonzero fill after {len} bits."
+        );
+        assert_eq!(
+            padding_zero_count(len),
+            fill.len(),
+            "Mismatch with sha256_pad on {len} bits."
+        );
+    }
+}
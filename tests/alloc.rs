@@ -0,0 +1,123 @@
+//! Allocation harness: measures the peak heap usage of the bit-per-byte and packed-byte paths,
+//! and the reallocations of padding.
+//! Kept in its own test binary so the counting allocator sees only the tests here, which take
+//! `SERIAL` so they do not disturb each other's peaks.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use kimchi::mina_curves::pasta::Fp;
+use sha256_kimchi::{native_sha256::NativeSha256, sha_helpers::*};
+
+struct CountingAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static SERIAL: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    static REALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            REALLOCS.with(|count| count.set(count.get() + 1));
+            let current = CURRENT.fetch_add(new_size, Ordering::SeqCst) + new_size;
+            PEAK.fetch_max(current, Ordering::SeqCst);
+            CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+/// Returns the result of `f` and the peak number of heap bytes it held at once.
+fn peak_of<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let base = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(base, Ordering::SeqCst);
+    let out = f();
+    (out, PEAK.load(Ordering::SeqCst) - base)
+}
+
+/// Tests that the packed-byte paths avoid the 8x memory of the bit-per-byte representation.
+#[test]
+fn peak_memory_test() {
+    let _serial = SERIAL.lock().unwrap_or_else(|err| err.into_inner());
+    let data = vec![0x61u8; 4 * 1024];
+    let max_bytes = data.len() + 64;
+
+    let (bit_digest, bit_peak) = peak_of(|| {
+        let (padded, _) = sha256_pad(bytes_to_bits(&data), 8 * max_bytes).unwrap();
+        NativeSha256::<Fp>::new(padded).hash()
+    });
+    let owned = data.clone();
+    let (packed_digest, packed_peak) = peak_of(move || {
+        let (padded, _) = sha256_pad_bytes(owned, max_bytes).unwrap();
+        NativeSha256::<Fp>::hash_packed(&padded)
+    });
+    let (stream_digest, stream_peak) = peak_of(|| NativeSha256::<Fp>::hash_bytes(&data));
+
+    assert_eq!(
+        bit_digest, packed_digest,
+        "Mismatch between bit and packed paths."
+    );
+    assert_eq!(
+        bit_digest, stream_digest,
+        "Mismatch between bit and streaming paths."
+    );
+    assert!(
+        bit_peak >= 8 * data.len(),
+        "Bit path must hold the expanded preimage, peaked at {bit_peak} bytes."
+    );
+    assert!(
+        packed_peak < 2 * data.len(),
+        "Packed path must stay near the input size, peaked at {packed_peak} bytes."
+    );
+    assert!(
+        stream_peak < data.len(),
+        "Streaming path must not buffer the input, peaked at {stream_peak} bytes."
+    );
+}
+
+/// Tests that padding grows the bit buffer with a single reallocation, however many blocks it adds.
+#[test]
+fn pad_reallocation_test() {
+    let _serial = SERIAL.lock().unwrap_or_else(|err| err.into_inner());
+
+    for max_bits in [512, 512 * 64] {
+        let bits = vec![1u8; 100];
+        let before = REALLOCS.with(Cell::get);
+        let (padded, _) = sha256_pad(bits, max_bits).unwrap();
+        let reallocs = REALLOCS.with(Cell::get) - before;
+
+        assert_eq!(padded.len(), max_bits, "Mismatch on padded length.");
+        assert_eq!(
+            reallocs, 1,
+            "Expected one reallocation for {max_bits} bits."
+        );
+    }
+}
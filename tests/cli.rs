@@ -0,0 +1,43 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+use sha2::{Digest, Sha256};
+
+/// Runs the `sha256` binary with `args`, feeding `input` on stdin.
+fn run(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sha256"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start the sha256 binary.");
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Tests that the CLI prints `sha256sum`-style output and honours `--check`.
+#[test]
+fn cli_test() {
+    let input = vec![0x42u8; 200];
+    let expected = hex::encode(Sha256::digest(&input));
+
+    let out = run(&[], &input);
+    assert!(out.status.success(), "Plain hashing failed.");
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        format!("{expected}  -\n"),
+        "Mismatch with sha256sum output."
+    );
+
+    let out = run(&["--check", &expected.to_uppercase()], &input);
+    assert!(out.status.success(), "Matching digest rejected.");
+
+    let out = run(&["--check", &expected], b"other input");
+    assert_eq!(out.status.code(), Some(1), "Mismatching digest accepted.");
+
+    let out = run(&["--check"], &input);
+    assert_eq!(out.status.code(), Some(2), "Missing argument accepted.");
+}
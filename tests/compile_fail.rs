@@ -0,0 +1,9 @@
+// trybuild drives a host `cargo`, which is not available under a wasm runner.
+#![cfg(not(target_arch = "wasm32"))]
+
+/// Tests that misuse of the public API is rejected at compile time.
+#[test]
+fn compile_fail_test() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
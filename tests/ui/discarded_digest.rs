@@ -0,0 +1,14 @@
+#![deny(unused_must_use)]
+
+use ark_ff::PrimeField;
+use sha256_kimchi::{
+    commitment::verify_commitment, native_sha256::NativeSha256, sha_helpers::digest_to_hex,
+};
+
+fn discard<F: PrimeField>(digest: [[F; 32]; 8]) {
+    NativeSha256::<F>::hash_bytes(b"abc");
+    digest_to_hex(digest);
+    verify_commitment(b"abc", &[0u8; 32], digest);
+}
+
+fn main() {}
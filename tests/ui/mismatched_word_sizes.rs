@@ -0,0 +1,10 @@
+use ark_ff::PrimeField;
+use sha256_kimchi::sha_helpers::{and, xor};
+
+// Bit-level helpers are generic over one word size `N`; mixing sizes must not compile.
+fn mix<F: PrimeField>(word: [F; 32], short: [F; 24]) {
+    let _ = xor(word, short);
+    let _ = and(short, word);
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+// Run with `wasm-pack test --node` or `cargo test --target wasm32-unknown-unknown` and the
+// `wasm-bindgen-test-runner`.
+#![cfg(target_arch = "wasm32")]
+
+use kimchi::mina_curves::pasta::Fp;
+use sha2::{Digest, Sha256};
+use sha256_kimchi::{native_sha256::NativeSha256, sha_helpers::*};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Tests that the core hash matches the standard SHA256 under wasm32.
+#[wasm_bindgen_test]
+fn wasm_hash_test() {
+    let (padded, _) = sha256_pad(bytes_to_bits(b"abc"), 512).unwrap();
+    let digest = NativeSha256::<Fp>::new(padded).hash();
+
+    assert_eq!(
+        digest_to_hex(digest),
+        hex::encode(Sha256::digest(b"abc")),
+        "Mismatch between native and standard SHA256 on wasm32."
+    );
+}
+
+/// Tests that the serial fallback of the parallel Merkle root matches the serial root.
+#[cfg(feature = "parallel")]
+#[wasm_bindgen_test]
+fn wasm_merkle_root_parallel_test() {
+    use sha256_kimchi::merkle::MerkleTree;
+
+    let leaves: Vec<[u8; 32]> = (0u8..5).map(|i| [i; 32]).collect();
+
+    assert_eq!(
+        MerkleTree::<Fp>::root_parallel(&leaves),
+        MerkleTree::<Fp>::root(&leaves),
+        "Mismatch between fallback and serial roots."
+    );
+}